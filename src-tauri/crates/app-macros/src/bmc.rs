@@ -0,0 +1,167 @@
+use crate::ipc::{self, IpcOpts, IpcTypes};
+use crate::utils::missing;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Ident, LitStr, Result, Type};
+
+#[derive(Default)]
+struct BmcOpts {
+	table: Option<LitStr>,
+	entity: Option<Type>,
+	for_create: Option<Type>,
+	for_update: Option<Type>,
+	filter: Option<Type>,
+	custom: Vec<Ident>,
+	ipc: Option<IpcOpts>,
+}
+
+impl BmcOpts {
+	fn from_input(input: &DeriveInput) -> Result<Self> {
+		let mut opts = BmcOpts::default();
+		for attr in input.attrs.iter().filter(|a| a.path().is_ident("bmc")) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("table") {
+					opts.table = Some(meta.value()?.parse()?);
+				} else if meta.path.is_ident("entity") {
+					opts.entity = Some(meta.value()?.parse()?);
+				} else if meta.path.is_ident("for_create") {
+					opts.for_create = Some(meta.value()?.parse()?);
+				} else if meta.path.is_ident("for_update") {
+					opts.for_update = Some(meta.value()?.parse()?);
+				} else if meta.path.is_ident("filter") {
+					opts.filter = Some(meta.value()?.parse()?);
+				} else if meta.path.is_ident("custom") {
+					meta.parse_nested_meta(|method| {
+						match method.path.get_ident() {
+							Some(ident) => opts.custom.push(ident.clone()),
+							None => return Err(method.error("expected a method name")),
+						}
+						Ok(())
+					})?;
+				} else if meta.path.is_ident("ipc") {
+					opts.ipc = Some(IpcOpts::parse(&meta)?);
+				} else {
+					return Err(meta.error("unsupported bmc option"));
+				}
+				Ok(())
+			})?;
+		}
+		Ok(opts)
+	}
+
+	fn is_custom(&self, method: &str) -> bool {
+		self.custom.iter().any(|ident| ident == method)
+	}
+}
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+	let name = &input.ident;
+	let opts = BmcOpts::from_input(&input)?;
+
+	let table = opts
+		.table
+		.as_ref()
+		.ok_or_else(|| missing(&input, "bmc", "table"))?;
+	let entity = opts
+		.entity
+		.as_ref()
+		.ok_or_else(|| missing(&input, "bmc", "entity"))?;
+
+	let mut methods = Vec::new();
+
+	if !opts.is_custom("get") {
+		methods.push(quote! {
+			pub async fn get(ctx: std::sync::Arc<crate::ctx::Ctx>, id: &str) -> crate::Result<#entity> {
+				crate::model::bmc_base::bmc_get::<#entity>(ctx, Self::ENTITY, id).await
+			}
+		});
+	}
+
+	let for_create = || {
+		opts.for_create
+			.as_ref()
+			.ok_or_else(|| missing(&input, "bmc", "for_create"))
+	};
+	let for_update = || {
+		opts.for_update
+			.as_ref()
+			.ok_or_else(|| missing(&input, "bmc", "for_update"))
+	};
+
+	if !opts.is_custom("create") {
+		let for_create = for_create()?;
+		methods.push(quote! {
+			pub async fn create(
+				ctx: std::sync::Arc<crate::ctx::Ctx>,
+				data: #for_create,
+			) -> crate::Result<crate::model::ModelMutateResultData> {
+				crate::model::bmc_base::bmc_create::<#entity, _>(ctx, Self::ENTITY, data).await
+			}
+		});
+	}
+
+	if !opts.is_custom("update") {
+		let for_update = for_update()?;
+		methods.push(quote! {
+			pub async fn update(
+				ctx: std::sync::Arc<crate::ctx::Ctx>,
+				id: &str,
+				data: #for_update,
+			) -> crate::Result<crate::model::ModelMutateResultData> {
+				crate::model::bmc_base::bmc_update::<#entity, _>(ctx, Self::ENTITY, id, data).await
+			}
+		});
+	}
+
+	if !opts.is_custom("delete") {
+		methods.push(quote! {
+			pub async fn delete(
+				ctx: std::sync::Arc<crate::ctx::Ctx>,
+				id: &str,
+			) -> crate::Result<crate::model::ModelMutateResultData> {
+				crate::model::bmc_base::bmc_delete::<#entity>(ctx, Self::ENTITY, id).await
+			}
+		});
+	}
+
+	if !opts.is_custom("list") {
+		let filter = opts
+			.filter
+			.as_ref()
+			.ok_or_else(|| missing(&input, "bmc", "filter"))?;
+		methods.push(quote! {
+			pub async fn list(
+				ctx: std::sync::Arc<crate::ctx::Ctx>,
+				filter: Option<#filter>,
+			) -> crate::Result<Vec<#entity>> {
+				crate::model::bmc_base::bmc_list(ctx, Self::ENTITY, filter, modql::ListOptions::default())
+					.await
+			}
+		});
+	}
+
+	// The commands call the Bmc methods, generated or custom (e.g., `TaskBmc::create` with its task rules).
+	let commands = match &opts.ipc {
+		Some(ipc_opts) => {
+			let types = IpcTypes {
+				bmc: name,
+				table,
+				entity,
+				for_create: for_create()?,
+				for_update: for_update()?,
+			};
+			ipc::commands(types, ipc_opts)
+		}
+		None => quote! {},
+	};
+
+	Ok(quote! {
+		impl #name {
+			const ENTITY: &'static str = #table;
+
+			#(#methods)*
+		}
+
+		#commands
+	})
+}
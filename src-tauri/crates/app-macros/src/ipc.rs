@@ -0,0 +1,172 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::meta::ParseNestedMeta;
+use syn::token::Paren;
+use syn::{Ident, LitStr, Result, Type};
+
+/// The `ipc(..)` option of the `Bmc` derive (e.g., `ipc(id = ProjectId)`, or just `ipc` for the defaults).
+#[derive(Default)]
+pub struct IpcOpts {
+	name: Option<LitStr>,
+	plural: Option<LitStr>,
+	id: Option<Type>,
+}
+
+impl IpcOpts {
+	pub fn parse(meta: &ParseNestedMeta) -> Result<Self> {
+		let mut opts = IpcOpts::default();
+		if !meta.input.peek(Paren) {
+			return Ok(opts);
+		}
+		meta.parse_nested_meta(|meta| {
+			if meta.path.is_ident("name") {
+				opts.name = Some(meta.value()?.parse()?);
+			} else if meta.path.is_ident("plural") {
+				opts.plural = Some(meta.value()?.parse()?);
+			} else if meta.path.is_ident("id") {
+				opts.id = Some(meta.value()?.parse()?);
+			} else {
+				return Err(meta.error("unsupported ipc option"));
+			}
+			Ok(())
+		})?;
+		Ok(opts)
+	}
+}
+
+/// The Bmc types used by the commands.
+pub struct IpcTypes<'a> {
+	pub bmc: &'a Ident,
+	pub table: &'a LitStr,
+	pub entity: &'a Type,
+	pub for_create: &'a Type,
+	pub for_update: &'a Type,
+}
+
+/// The `<name>_commands` module with the CRUD Tauri commands calling the Bmc (e.g., `get_project`, ..., `list_projects`).
+pub fn commands(types: IpcTypes, opts: &IpcOpts) -> TokenStream {
+	let IpcTypes {
+		bmc,
+		table,
+		entity,
+		for_create,
+		for_update,
+	} = types;
+
+	let name = opts
+		.name
+		.as_ref()
+		.map(|n| n.value())
+		.unwrap_or_else(|| table.value());
+	let plural = opts
+		.plural
+		.as_ref()
+		.map(|p| p.value())
+		.unwrap_or_else(|| format!("{name}s"));
+	let id = match &opts.id {
+		Some(id) => quote! { #id },
+		None => quote! { String },
+	};
+
+	let module = format_ident!("{}_commands", name);
+	let module_doc =
+		format!(" The CRUD Tauri commands of `{bmc}` (generated by the `Bmc` derive).");
+
+	let get_fn = format_ident!("get_{}", name);
+	let create_fn = format_ident!("create_{}", name);
+	let update_fn = format_ident!("update_{}", name);
+	let delete_fn = format_ident!("delete_{}", name);
+	let list_fn = format_ident!("list_{}", plural);
+
+	quote! {
+		#[doc = #module_doc]
+		pub mod #module {
+			#[allow(unused_imports)]
+			use super::*;
+
+			// Note: The ids and data are deserialized here (rather than by Tauri) to return an IpcError on invalid params
+			//       (e.g., a task id for a project).
+
+			#[tauri::command]
+			#[tracing::instrument(skip_all, fields(params = %crate::ipc::ParamsSummary::summary(&params), request_id = request_id.as_deref(), outcome))]
+			pub async fn #get_fn(
+				window: tauri::Window<tauri::Wry>,
+				request_id: Option<String>,
+				params: crate::ipc::GetParams<serde_json::Value>,
+			) -> crate::ipc::IpcResponse<#entity> {
+				match crate::ctx::Ctx::from_window(window, request_id) {
+					Ok(ctx) => match serde_json::from_value::<#id>(params.id) {
+						Ok(id) => #bmc::get(ctx, &id).await.into(),
+						Err(err) => Err(crate::Error::JsonSerde(err)).into(),
+					},
+					Err(_) => Err(crate::Error::CtxFail).into(),
+				}
+			}
+
+			#[tauri::command]
+			#[tracing::instrument(skip_all, fields(params = %crate::ipc::ParamsSummary::summary(&params), request_id = request_id.as_deref(), outcome))]
+			pub async fn #create_fn(
+				window: tauri::Window<tauri::Wry>,
+				request_id: Option<String>,
+				params: crate::ipc::CreateParams<serde_json::Value>,
+			) -> crate::ipc::IpcResponse<crate::model::ModelMutateResultData> {
+				match crate::ctx::Ctx::from_window(window, request_id) {
+					Ok(ctx) => match serde_json::from_value::<#for_create>(params.data) {
+						Ok(data) => #bmc::create(ctx, data).await.into(),
+						Err(err) => Err(crate::Error::JsonSerde(err)).into(),
+					},
+					Err(_) => Err(crate::Error::CtxFail).into(),
+				}
+			}
+
+			#[tauri::command]
+			#[tracing::instrument(skip_all, fields(params = %crate::ipc::ParamsSummary::summary(&params), request_id = request_id.as_deref(), outcome))]
+			pub async fn #update_fn(
+				window: tauri::Window<tauri::Wry>,
+				request_id: Option<String>,
+				params: crate::ipc::UpdateParams<#for_update, serde_json::Value>,
+			) -> crate::ipc::IpcResponse<crate::model::ModelMutateResultData> {
+				match crate::ctx::Ctx::from_window(window, request_id) {
+					Ok(ctx) => match serde_json::from_value::<#id>(params.id) {
+						Ok(id) => #bmc::update(ctx, &id, params.data).await.into(),
+						Err(err) => Err(crate::Error::JsonSerde(err)).into(),
+					},
+					Err(_) => Err(crate::Error::CtxFail).into(),
+				}
+			}
+
+			#[tauri::command]
+			#[tracing::instrument(skip_all, fields(params = %crate::ipc::ParamsSummary::summary(&params), request_id = request_id.as_deref(), outcome))]
+			pub async fn #delete_fn(
+				window: tauri::Window<tauri::Wry>,
+				request_id: Option<String>,
+				params: crate::ipc::DeleteParams<serde_json::Value>,
+			) -> crate::ipc::IpcResponse<crate::model::ModelMutateResultData> {
+				match crate::ctx::Ctx::from_window(window, request_id) {
+					Ok(ctx) => match serde_json::from_value::<#id>(params.id) {
+						Ok(id) => #bmc::delete(ctx, &id).await.into(),
+						Err(err) => Err(crate::Error::JsonSerde(err)).into(),
+					},
+					Err(_) => Err(crate::Error::CtxFail).into(),
+				}
+			}
+
+			/// Note: The filter is deserialized here (rather than by Tauri) to return an IpcError on invalid filter.
+			#[tauri::command]
+			#[tracing::instrument(skip_all, fields(params = %crate::ipc::ParamsSummary::summary(&params), request_id = request_id.as_deref(), outcome))]
+			pub async fn #list_fn(
+				window: tauri::Window<tauri::Wry>,
+				request_id: Option<String>,
+				params: crate::ipc::ListParams<serde_json::Value>,
+			) -> crate::ipc::IpcResponse<Vec<#entity>> {
+				match crate::ctx::Ctx::from_window(window, request_id) {
+					Ok(ctx) => match params.filter.map(serde_json::from_value).transpose() {
+						Ok(filter) => #bmc::list(ctx, filter).await.into(),
+						Err(err) => Err(crate::Error::JsonSerde(err)).into(),
+					},
+					Err(_) => Err(crate::Error::CtxFail).into(),
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,57 @@
+//! Derive macros for the app entity boilerplate.
+//!
+//! - `Creatable` / `Patchable` - The store traits of the `*ForCreate` / `*ForUpdate` (serialized with their `Serialize`).
+//! - `Bmc` - The `ENTITY` const and the CRUD forwarding methods (`get`, `create`, `update`, `delete`, `list`) of a `*Bmc`,
+//!   and with `ipc(..)`, the CRUD Tauri commands (e.g., `get_project`, ..., `list_projects`) calling them.
+//!
+//! Notes:
+//!   - The generated code uses the app crate paths (e.g., `crate::model::store::Creatable`, `crate::model::bmc_base`),
+//!     so these macros are only meant for the app crate.
+//!   - See the attribute docs on each derive for the supported options.
+//!   - The expansions are compile tested against a stub of the app crate paths (see `tests/ui`).
+
+mod bmc;
+mod ipc;
+mod store;
+mod utils;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// `crate::model::store::Creatable`, for a type also deriving `Serialize` (the `None` fields are not stored).
+///
+/// With the `Option` field option:
+/// - `#[store(default = expr)]` - Value when `None` (e.g., `#[store(default = false)]`).
+#[proc_macro_derive(Creatable, attributes(store))]
+pub fn derive_creatable(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	store::derive(input, store::StoreTrait::Creatable)
+		.unwrap_or_else(|err| err.to_compile_error())
+		.into()
+}
+
+/// Same as `Creatable`, for `crate::model::store::Patchable` (without `#[store(default = ..)]`, the `None` fields are not changed).
+#[proc_macro_derive(Patchable, attributes(store))]
+pub fn derive_patchable(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	store::derive(input, store::StoreTrait::Patchable)
+		.unwrap_or_else(|err| err.to_compile_error())
+		.into()
+}
+
+/// The `ENTITY` const and the CRUD methods of a Backend Model Controller, forwarding to the `bmc_*` functions.
+///
+/// `#[bmc(table = "project", entity = Project, for_create = .., for_update = .., filter = .., custom(list), ipc(..))]`
+/// - `custom(..)` - The methods implemented by hand (e.g., with extra rules), which are not generated.
+/// - `ipc(..)` - Also generate the CRUD Tauri commands calling the Bmc methods (generated or custom),
+///   in a `<name>_commands` module (e.g., `project_commands::get_project`), with the options:
+///   - `name` - Defaults to the `table` (e.g., `get_project`).
+///   - `plural` - Defaults to `name` + `s` (for the list command).
+///   - `id` - The id type of the get/update/delete params (e.g., `ProjectId`), defaults to `String`.
+#[proc_macro_derive(Bmc, attributes(bmc))]
+pub fn derive_bmc(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	bmc::derive(input)
+		.unwrap_or_else(|err| err.to_compile_error())
+		.into()
+}
@@ -0,0 +1,70 @@
+use crate::utils::{named_fields, option_inner};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Error, Expr, Field, Result};
+
+pub enum StoreTrait {
+	Creatable,
+	Patchable,
+}
+
+fn default_expr(field: &Field) -> Result<Option<Expr>> {
+	let mut default = None;
+	for attr in field.attrs.iter().filter(|a| a.path().is_ident("store")) {
+		attr.parse_nested_meta(|meta| {
+			if meta.path.is_ident("default") {
+				default = Some(meta.value()?.parse::<Expr>()?);
+				Ok(())
+			} else {
+				Err(meta.error("unsupported store option"))
+			}
+		})?;
+	}
+	Ok(default)
+}
+
+pub fn derive(input: DeriveInput, store_trait: StoreTrait) -> Result<TokenStream> {
+	let name = &input.ident;
+
+	let mut defaults = Vec::new();
+	for field in named_fields(&input)? {
+		let default = match default_expr(field)? {
+			Some(default) => default,
+			None => continue,
+		};
+		if let StoreTrait::Patchable = store_trait {
+			return Err(Error::new_spanned(
+				field,
+				"`#[store(default = ..)]` is only for Creatable (a patch only changes the given properties)",
+			));
+		}
+		if option_inner(&field.ty).is_none() {
+			return Err(Error::new_spanned(
+				field,
+				"`#[store(default = ..)]` is only for Option fields",
+			));
+		}
+		let prop = field.ident.as_ref().expect("named field").to_string();
+		defaults.push(quote! {
+			(#prop, surrealdb::sql::Value::from(#default))
+		});
+	}
+
+	let store_impl = match store_trait {
+		StoreTrait::Creatable if defaults.is_empty() => quote! {
+			impl crate::model::store::Creatable for #name {}
+		},
+		StoreTrait::Creatable => quote! {
+			impl crate::model::store::Creatable for #name {
+				fn defaults() -> Vec<(&'static str, surrealdb::sql::Value)> {
+					vec![#(#defaults),*]
+				}
+			}
+		},
+		StoreTrait::Patchable => quote! {
+			impl crate::model::store::Patchable for #name {}
+		},
+	};
+
+	Ok(store_impl)
+}
@@ -0,0 +1,42 @@
+use syn::{Data, DeriveInput, Error, Field, Fields, GenericArgument, PathArguments, Result, Type};
+
+/// The named fields of a struct (the only supported input of the derives).
+pub fn named_fields(input: &DeriveInput) -> Result<Vec<&Field>> {
+	match &input.data {
+		Data::Struct(data) => match &data.fields {
+			Fields::Named(fields) => Ok(fields.named.iter().collect()),
+			_ => Err(Error::new_spanned(
+				&input.ident,
+				"only structs with named fields are supported",
+			)),
+		},
+		_ => Err(Error::new_spanned(
+			&input.ident,
+			"only structs are supported",
+		)),
+	}
+}
+
+/// The `T` of an `Option<T>` type (None if not an Option).
+pub fn option_inner(ty: &Type) -> Option<&Type> {
+	let path = match ty {
+		Type::Path(type_path) if type_path.qself.is_none() => &type_path.path,
+		_ => return None,
+	};
+	let segment = path.segments.last()?;
+	if segment.ident != "Option" {
+		return None;
+	}
+	match &segment.arguments {
+		PathArguments::AngleBracketed(args) => match args.args.first() {
+			Some(GenericArgument::Type(inner)) => Some(inner),
+			_ => None,
+		},
+		_ => None,
+	}
+}
+
+/// Error for a missing required attribute option (e.g., `#[bmc(table = "..")]`).
+pub fn missing(input: &DeriveInput, attr: &str, option: &str) -> Error {
+	Error::new_spanned(&input.ident, format!("missing `#[{attr}({option} = ..)]`"))
+}
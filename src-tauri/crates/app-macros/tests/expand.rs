@@ -0,0 +1,14 @@
+//! Compile tests of the derive expansions (see `tests/ui`).
+//!
+//! Notes:
+//!   - The `pass` cases stub the app crate paths used by the generated code (e.g., `crate::ipc::GetParams`),
+//!     so they check that the expansion is valid Rust against these paths.
+//!   - The `.stderr` of the `compile_fail` cases can be regenerated with `TRYBUILD=overwrite cargo test`.
+
+#[test]
+fn test_expand_bmc() {
+	let t = trybuild::TestCases::new();
+	t.pass("tests/ui/bmc_ipc.rs");
+	t.compile_fail("tests/ui/bmc_ipc_missing_for_create.rs");
+	t.compile_fail("tests/ui/bmc_ipc_unsupported_option.rs");
+}
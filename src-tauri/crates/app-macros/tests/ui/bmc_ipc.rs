@@ -0,0 +1,218 @@
+//! The `Bmc` derive with `ipc(..)`, against a stub of the app crate paths.
+
+use app_macros::Bmc;
+use serde::{Deserialize, Serialize};
+
+// region:    --- App Stub
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+	CtxFail,
+	JsonSerde(serde_json::Error),
+}
+
+mod ctx {
+	use std::sync::Arc;
+	use tauri::{Window, Wry};
+
+	pub struct Ctx;
+
+	impl Ctx {
+		pub fn from_window(
+			_window: Window<Wry>,
+			_request_id: Option<String>,
+		) -> crate::Result<Arc<Ctx>> {
+			Ok(Arc::new(Ctx))
+		}
+	}
+}
+
+mod ipc {
+	use serde::{Deserialize, Serialize};
+
+	#[derive(Deserialize)]
+	pub struct GetParams<I = String> {
+		pub id: I,
+	}
+
+	#[derive(Deserialize)]
+	pub struct CreateParams<D> {
+		pub data: D,
+	}
+
+	#[derive(Deserialize)]
+	pub struct UpdateParams<D, I = String> {
+		pub id: I,
+		pub data: D,
+	}
+
+	#[derive(Deserialize)]
+	pub struct DeleteParams<I = String> {
+		pub id: I,
+	}
+
+	#[derive(Deserialize)]
+	pub struct ListParams<F> {
+		pub filter: Option<F>,
+	}
+
+	pub trait ParamsSummary {
+		fn summary(&self) -> String;
+	}
+
+	impl<T> ParamsSummary for T {
+		fn summary(&self) -> String {
+			String::new()
+		}
+	}
+
+	#[derive(Serialize)]
+	pub struct IpcResponse<D: Serialize> {
+		result: Option<D>,
+	}
+
+	impl<D: Serialize> From<crate::Result<D>> for IpcResponse<D> {
+		fn from(res: crate::Result<D>) -> Self {
+			IpcResponse { result: res.ok() }
+		}
+	}
+}
+
+mod model {
+	use serde::Serialize;
+
+	#[derive(Serialize)]
+	pub struct ModelMutateResultData {
+		pub id: String,
+	}
+
+	pub mod bmc_base {
+		use super::ModelMutateResultData;
+		use crate::ctx::Ctx;
+		use std::sync::Arc;
+
+		pub async fn bmc_get<E>(
+			_ctx: Arc<Ctx>,
+			_entity: &'static str,
+			_id: &str,
+		) -> crate::Result<E> {
+			unimplemented!()
+		}
+
+		pub async fn bmc_create<E, D>(
+			_ctx: Arc<Ctx>,
+			_entity: &'static str,
+			_data: D,
+		) -> crate::Result<ModelMutateResultData> {
+			unimplemented!()
+		}
+
+		pub async fn bmc_update<E, D>(
+			_ctx: Arc<Ctx>,
+			_entity: &'static str,
+			_id: &str,
+			_data: D,
+		) -> crate::Result<ModelMutateResultData> {
+			unimplemented!()
+		}
+
+		pub async fn bmc_delete<E>(
+			_ctx: Arc<Ctx>,
+			_entity: &'static str,
+			_id: &str,
+		) -> crate::Result<ModelMutateResultData> {
+			unimplemented!()
+		}
+
+		pub async fn bmc_list<E, F>(
+			_ctx: Arc<Ctx>,
+			_entity: &'static str,
+			_filter: Option<F>,
+			_list_options: modql::ListOptions,
+		) -> crate::Result<Vec<E>> {
+			unimplemented!()
+		}
+	}
+}
+
+// endregion: --- App Stub
+
+// region:    --- Entities
+
+#[derive(Deserialize)]
+pub struct ProjectId(String);
+
+impl std::ops::Deref for ProjectId {
+	type Target = str;
+	fn deref(&self) -> &str {
+		&self.0
+	}
+}
+
+#[derive(Serialize)]
+pub struct Project {
+	pub id: String,
+}
+
+#[derive(Deserialize)]
+pub struct ProjectForCreate {
+	pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct ProjectForUpdate {
+	pub name: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ProjectFilter {
+	pub name: Option<String>,
+}
+
+/// The generated methods, and the commands with a typed id.
+#[derive(Bmc)]
+#[bmc(
+	table = "project",
+	entity = Project,
+	for_create = ProjectForCreate,
+	for_update = ProjectForUpdate,
+	filter = ProjectFilter,
+	ipc(id = ProjectId)
+)]
+pub struct ProjectBmc;
+
+/// The commands calling a custom method, with a name and plural.
+#[derive(Bmc)]
+#[bmc(
+	table = "project",
+	entity = Project,
+	for_create = ProjectForCreate,
+	for_update = ProjectForUpdate,
+	custom(list),
+	ipc(name = "archive", plural = "archives")
+)]
+pub struct ArchiveBmc;
+
+impl ArchiveBmc {
+	pub async fn list(
+		_ctx: std::sync::Arc<ctx::Ctx>,
+		_filter: Option<ProjectFilter>,
+	) -> Result<Vec<Project>> {
+		Ok(Vec::new())
+	}
+}
+
+// endregion: --- Entities
+
+fn main() {
+	let _ = project_commands::get_project;
+	let _ = project_commands::create_project;
+	let _ = project_commands::update_project;
+	let _ = project_commands::delete_project;
+	let _ = project_commands::list_projects;
+
+	let _ = archive_commands::get_archive;
+	let _ = archive_commands::list_archives;
+}
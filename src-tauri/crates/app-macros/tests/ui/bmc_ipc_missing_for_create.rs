@@ -0,0 +1,12 @@
+//! The `ipc(..)` commands need the `for_create` type, even with a custom `create`.
+
+use app_macros::Bmc;
+
+pub struct Project;
+pub struct ProjectForUpdate;
+
+#[derive(Bmc)]
+#[bmc(table = "project", entity = Project, for_update = ProjectForUpdate, custom(create, list), ipc)]
+pub struct ProjectBmc;
+
+fn main() {}
@@ -0,0 +1,20 @@
+//! An unknown `ipc(..)` option is an error (rather than ignored).
+
+use app_macros::Bmc;
+
+pub struct Project;
+pub struct ProjectForCreate;
+pub struct ProjectForUpdate;
+
+#[derive(Bmc)]
+#[bmc(
+	table = "project",
+	entity = Project,
+	for_create = ProjectForCreate,
+	for_update = ProjectForUpdate,
+	custom(list),
+	ipc(id = String, prefix = "app")
+)]
+pub struct ProjectBmc;
+
+fn main() {}
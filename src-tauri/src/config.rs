@@ -0,0 +1,284 @@
+//! Typed application configuration, loaded at startup (see `main`).
+//!
+//! The `config.toml` file of the app config dir (e.g., `~/.config/<bundle identifier>/` on Linux) is optional,
+//! all of its properties having a default. The environment variables override the file properties (e.g., for dev).
+//!
+//! ```toml
+//! [seed]
+//! mode = "if_empty"          # or "always", "never"
+//! projects = 2
+//! tasks_per_project = 200
+//!
+//! [log]
+//! level = "info"             # "error", "warn", "info", "debug", "trace"
+//!
+//! [event]
+//! window_ms = 50             # Model event coalescing window, 0 to emit right away
+//! ```
+//!
+//! Notes:
+//!   - Env overrides: `APP_CONFIG` (config file path), `APP_SEED_MODE`, `APP_SEED_PROJECTS`,
+//!     `APP_SEED_TASKS_PER_PROJECT`, `APP_LOG_LEVEL`, `APP_EVENT_WINDOW_MS`.
+//!   - The unknown properties are errors (e.g., typos), as well as the invalid values, with the property in the message.
+//!   - No store property, as the store is in memory (this build has only the surrealdb `kv-mem` feature).
+//!
+
+use crate::prelude::*;
+use crate::{Error, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Name of the config file, in the app config dir.
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Max of `event.window_ms`, a larger window would delay the UI updates too much.
+const EVENT_WINDOW_MS_MAX: u64 = 5000;
+
+// region:    --- AppConfig
+
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppConfig {
+	pub seed: SeedConfig,
+	pub log: LogConfig,
+	pub event: EventConfig,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct SeedConfig {
+	pub mode: SeedMode,
+	pub projects: usize,
+	pub tasks_per_project: usize,
+}
+
+impl Default for SeedConfig {
+	fn default() -> Self {
+		SeedConfig {
+			mode: SeedMode::default(),
+			projects: 2,
+			tasks_per_project: 200,
+		}
+	}
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SeedMode {
+	/// Seed when the store has no projects (i.e., always for the memory store).
+	#[default]
+	IfEmpty,
+	Always,
+	Never,
+}
+
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogConfig {
+	pub level: LogLevel,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+	Error,
+	Warn,
+	#[default]
+	Info,
+	Debug,
+	Trace,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct EventConfig {
+	/// The window of the `Model` event coalescing (see `model::event_coalescer`).
+	pub window_ms: u64,
+}
+
+impl Default for EventConfig {
+	fn default() -> Self {
+		EventConfig { window_ms: 50 }
+	}
+}
+
+impl EventConfig {
+	pub fn window(&self) -> Duration {
+		Duration::from_millis(self.window_ms)
+	}
+}
+
+// endregion: --- AppConfig
+
+// region:    --- Load
+
+impl AppConfig {
+	/// Load the config of the app config dir (or of `APP_CONFIG`), with the environment overrides, and validate it.
+	pub fn load(app_config_dir: &Path) -> Result<Self> {
+		let env = |name: &str| std::env::var(name).ok();
+
+		let config = match env("APP_CONFIG") {
+			// Note: An explicit config file must exist.
+			Some(file) => Self::from_file(Path::new(&file))?,
+			None => {
+				let file = app_config_dir.join(CONFIG_FILE_NAME);
+				if file.exists() {
+					Self::from_file(&file)?
+				} else {
+					AppConfig::default()
+				}
+			}
+		};
+
+		let config = config.with_env(env)?;
+		config.validate()?;
+		Ok(config)
+	}
+
+	fn from_file(file: &Path) -> Result<Self> {
+		let content = fs::read_to_string(file)
+			.map_err(|ex| Error::ConfigFileRead(f!("{}: {ex}", file.display())))?;
+		Self::from_toml(&content).map_err(|err| match err {
+			Error::ConfigParse(msg) => Error::ConfigParse(f!("{}: {msg}", file.display())),
+			err => err,
+		})
+	}
+
+	fn from_toml(content: &str) -> Result<Self> {
+		toml::from_str(content).map_err(|ex| Error::ConfigParse(ex.to_string()))
+	}
+
+	/// Override the properties with the environment variables (`env` returning the value of the variable, if set).
+	fn with_env(mut self, env: impl Fn(&str) -> Option<String>) -> Result<Self> {
+		if let Some(val) = env("APP_SEED_MODE") {
+			self.seed.mode = parse_env_enum("APP_SEED_MODE", &val)?;
+		}
+		if let Some(val) = env("APP_SEED_PROJECTS") {
+			self.seed.projects = parse_env_num("APP_SEED_PROJECTS", &val)?;
+		}
+		if let Some(val) = env("APP_SEED_TASKS_PER_PROJECT") {
+			self.seed.tasks_per_project = parse_env_num("APP_SEED_TASKS_PER_PROJECT", &val)?;
+		}
+		if let Some(val) = env("APP_LOG_LEVEL") {
+			self.log.level = parse_env_enum("APP_LOG_LEVEL", &val)?;
+		}
+		if let Some(val) = env("APP_EVENT_WINDOW_MS") {
+			self.event.window_ms = parse_env_num("APP_EVENT_WINDOW_MS", &val)?;
+		}
+		Ok(self)
+	}
+
+	fn validate(&self) -> Result<()> {
+		if self.event.window_ms > EVENT_WINDOW_MS_MAX {
+			return Err(Error::ConfigInvalid(f!(
+				"event.window_ms: must be at most {EVENT_WINDOW_MS_MAX}, got {}",
+				self.event.window_ms
+			)));
+		}
+		Ok(())
+	}
+}
+
+// endregion: --- Load
+
+// region:    --- Config Utils
+
+/// Parse the value of an enum env variable, as in the config file (e.g., `if_empty`).
+fn parse_env_enum<T: for<'de> Deserialize<'de>>(name: &str, val: &str) -> Result<T> {
+	T::deserialize(serde::de::value::StrDeserializer::<serde::de::value::Error>::new(val))
+		.map_err(|ex| Error::ConfigInvalid(f!("{name}: {ex}")))
+}
+
+fn parse_env_num<T: FromStr>(name: &str, val: &str) -> Result<T> {
+	val.parse()
+		.map_err(|_| Error::ConfigInvalid(f!("{name}: not a non-negative integer: {val}")))
+}
+
+// endregion: --- Config Utils
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::HashMap;
+
+	#[test]
+	fn test_config_from_toml() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let content = r#"
+			[seed]
+			mode = "never"
+
+			[event]
+			window_ms = 0
+		"#;
+
+		// --- EXEC
+		let config = AppConfig::from_toml(content)?;
+
+		// --- CHECK
+		assert_eq!(config.seed.mode, SeedMode::Never);
+		assert_eq!(config.seed.projects, 2, "default of the missing property");
+		assert_eq!(config.log.level, LogLevel::Info);
+		assert_eq!(config.event.window(), Duration::ZERO);
+		assert_eq!(AppConfig::from_toml("")?, AppConfig::default());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_config_invalid() -> anyhow::Result<()> {
+		// --- EXEC & CHECK - parse
+		for content in [
+			"[store]\nengine = \"memory\"",
+			"[seed]\nprojects = -1",
+			"[event]\nwindow = 10",
+		] {
+			assert!(
+				matches!(AppConfig::from_toml(content), Err(Error::ConfigParse(_))),
+				"{content}"
+			);
+		}
+
+		// --- EXEC & CHECK - validate
+		let config = AppConfig::from_toml("[event]\nwindow_ms = 60000")?;
+		assert!(
+			matches!(config.validate(), Err(Error::ConfigInvalid(msg)) if msg.starts_with("event.window_ms"))
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_config_with_env() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let vars: HashMap<&str, &str> = [
+			("APP_SEED_MODE", "always"),
+			("APP_SEED_PROJECTS", "5"),
+			("APP_LOG_LEVEL", "debug"),
+		]
+		.into_iter()
+		.collect();
+		let env = |name: &str| vars.get(name).map(|val| val.to_string());
+
+		// --- EXEC
+		let config =
+			AppConfig::from_toml("[seed]\nprojects = 3\ntasks_per_project = 10")?.with_env(env)?;
+
+		// --- CHECK
+		assert_eq!(config.seed.mode, SeedMode::Always);
+		assert_eq!(config.seed.projects, 5, "env over file");
+		assert_eq!(config.seed.tasks_per_project, 10);
+		assert_eq!(config.log.level, LogLevel::Debug);
+
+		// --- CHECK - invalid values
+		let res = AppConfig::default().with_env(|_| Some("lots".to_string()));
+		assert!(matches!(res, Err(Error::ConfigInvalid(msg)) if msg.starts_with("APP_SEED_MODE")));
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
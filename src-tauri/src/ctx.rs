@@ -4,40 +4,106 @@
 //! Notes:
 //!     - Simple implementation for now.
 //!     - For cloud applications, this will be used for authorization.
-//!     - Eventually, this will also be used for "full context" logging/tracing or even performance tracing.
+//!     - The IPC calls are traced by layer (IPC command, BMC, store), with the `request_id` on the command span (see `logging`).
 //!     - For a single user, desktop application, this object is much simpler as authorization and logging requirements are much reduced.
+//!     - It does not depend on the Tauri app (only `from_window` does), the events go to the `EventBus` (the frontend being one of its listeners).
 
-use crate::event::HubEvent;
+use crate::event::{EventBus, HubEvent};
 use crate::model::ModelStore;
 use crate::Result;
 use serde::Serialize;
 use std::sync::Arc;
-use tauri::{AppHandle, Manager, Wry};
+use tauri::{Manager, Window, Wry};
 
 pub struct Ctx {
 	model_manager: Arc<ModelStore>,
-	app_handle: AppHandle<Wry>,
+	event_bus: Arc<EventBus>,
+	/// The label of the calling window (none when not from an IPC call, e.g., tests).
+	window_label: Option<String>,
+	/// The client request id of the IPC call, if given (put on its `Model` events).
+	request_id: Option<String>,
+	/// The database of the active workspace when the ctx was created, the one of all of its store calls
+	/// (i.e., a workspace switch does not split an IPC call across databases).
+	workspace_db: String,
 }
 
 impl Ctx {
-	pub fn from_app(app: AppHandle<Wry>) -> Result<Arc<Ctx>> {
-		Ok(Arc::new(Ctx::new(app)))
+	/// From the calling window, with the Tauri app states (i.e., the `ModelStore` and `EventBus` managed in `main`).
+	pub fn from_window(window: Window<Wry>, request_id: Option<String>) -> Result<Arc<Ctx>> {
+		let model_manager = (*window.state::<Arc<ModelStore>>()).clone();
+		let event_bus = (*window.state::<Arc<EventBus>>()).clone();
+		let ctx = Ctx {
+			window_label: Some(window.label().to_string()),
+			request_id,
+			..Ctx::new(model_manager, event_bus)
+		};
+		Ok(Arc::new(ctx))
 	}
 }
 
 impl Ctx {
-	pub fn new(app_handle: AppHandle<Wry>) -> Self {
+	/// Note: Not tied to Tauri, so the Backend Model Controllers can be run headless (e.g., in tests).
+	pub fn new(model_manager: Arc<ModelStore>, event_bus: Arc<EventBus>) -> Self {
 		Ctx {
-			model_manager: (*app_handle.state::<Arc<ModelStore>>()).clone(),
-			app_handle,
+			workspace_db: model_manager.active_db(),
+			model_manager,
+			event_bus,
+			window_label: None,
+			request_id: None,
 		}
 	}
 
+	/// A new ctx of the same states and window, with the client request id (`from_window` sets it from the call).
+	#[cfg(test)]
+	pub fn with_request_id(&self, request_id: impl Into<String>) -> Arc<Ctx> {
+		Arc::new(Ctx {
+			model_manager: self.model_manager.clone(),
+			event_bus: self.event_bus.clone(),
+			window_label: self.window_label.clone(),
+			request_id: Some(request_id.into()),
+			workspace_db: self.workspace_db.clone(),
+		})
+	}
+
+	/// A new ctx of the same states, as if from the window of the label (see `from_window`),
+	/// e.g., to emit the events of a subscription to its subscriber (see `EventTarget::Origin`).
+	pub fn with_window_label(&self, window_label: impl Into<String>) -> Arc<Ctx> {
+		self.with_window(Some(window_label.into()))
+	}
+
+	/// A new ctx of the same states, as if not from a window (i.e., `EventTarget::Origin` is all of the windows).
+	pub fn without_window_label(&self) -> Arc<Ctx> {
+		self.with_window(None)
+	}
+
+	fn with_window(&self, window_label: Option<String>) -> Arc<Ctx> {
+		Arc::new(Ctx {
+			model_manager: self.model_manager.clone(),
+			event_bus: self.event_bus.clone(),
+			window_label,
+			request_id: self.request_id.clone(),
+			workspace_db: self.workspace_db.clone(),
+		})
+	}
+
+	pub fn window_label(&self) -> Option<&str> {
+		self.window_label.as_deref()
+	}
+
+	pub fn request_id(&self) -> Option<&str> {
+		self.request_id.as_deref()
+	}
+
+	pub fn workspace_db(&self) -> &str {
+		&self.workspace_db
+	}
+
 	pub fn get_model_manager(&self) -> Arc<ModelStore> {
 		self.model_manager.clone()
 	}
 
-	pub fn emit_hub_event<D: Serialize + Clone>(&self, hub_event: HubEvent<D>) {
-		let _ = self.app_handle.emit_all("HubEvent", hub_event);
+	/// Publish the event on the event bus (see `event::EventBus`).
+	pub fn emit_hub_event<D: Serialize + Clone>(self: &Arc<Self>, hub_event: HubEvent<D>) {
+		self.event_bus.publish(self, hub_event);
 	}
 }
@@ -17,14 +17,81 @@ pub enum Error {
 
 	XPropertyNotFound(String),
 
+	/// Serde conversion error of a store value (see `model::store::surreal_serde`).
+	XValueSerde(String),
+
 	StoreFailToCreate(String),
 
+	/// The id of the record not found.
+	StoreRecordNotFound(String),
+
+	/// The id not of the expected table (e.g., a task id for a project).
+	StoreIdNotOfTable(String),
+
+	StoreDbNameInvalid(String),
+
 	Modql(modql::Error),
 
 	JsonSerde(serde_json::Error),
 
 	ModqlOperatorNotSupported(String),
 
+	RecurRuleInvalid(String),
+
+	DateInvalid(String),
+
+	/// All of the violations of the data (see `model::validate`).
+	Validation(Vec<crate::model::FieldViolation>),
+
+	TaskDepInvalid(String),
+
+	/// The dependency path (task ids) that would form the cycle.
+	TaskDepCycle(Vec<String>),
+
+	/// The ids of the open tasks blocking the task.
+	TaskBlocked(Vec<String>),
+
+	/// The id of the archived project.
+	ProjectArchived(String),
+
+	/// The id of the project which is not a template.
+	ProjectNotTemplate(String),
+
+	/// The id of the active workspace.
+	WorkspaceActive(String),
+
+	AppDataDirNotFound,
+
+	AppConfigDirNotFound,
+
+	AppLogDirNotFound,
+
+	/// The config file path, and the read error.
+	ConfigFileRead(String),
+
+	/// The TOML error of the config file (e.g., unknown property, invalid value).
+	ConfigParse(String),
+
+	/// The property (or env variable) and why it is invalid (see `config::AppConfig::validate`).
+	ConfigInvalid(String),
+
+	/// The entity of the list subscription (see `model::subscription`).
+	SubscriptionEntityNotSupported(String),
+
+	AttachmentSourceNotFile(String),
+
+	AttachmentFailToStore(String),
+
+	/// The id of the running time entry.
+	TimerAlreadyRunning(String),
+
+	TimerNotRunning,
+
+	/// The label of the window not open.
+	WindowNotFound(String),
+
+	Tauri(tauri::Error),
+
 	Surreal(surrealdb::err::Error),
 
 	IO(std::io::Error),
@@ -46,6 +113,11 @@ impl From<surrealdb::err::Error> for Error {
 		Error::Surreal(val)
 	}
 }
+impl From<tauri::Error> for Error {
+	fn from(val: tauri::Error) -> Self {
+		Error::Tauri(val)
+	}
+}
 impl From<std::io::Error> for Error {
 	fn from(val: std::io::Error) -> Self {
 		Error::IO(val)
@@ -1,9 +1,26 @@
 //! Event layer of the backend.
 //!
-//! Right now, very simple, just a HubEvent data type.
+//! The `HubEvent`s (e.g., the `Model` events) are published on the in-process `EventBus` (see `Ctx::emit_hub_event`),
+//! which calls all of its listeners, registered at startup (see `main`).
+//!
+//! Notes:
+//!   - The data of the event is serialized once (to a JSON Value) for all of the listeners.
+//!   - The sync listeners (`HubListener`) are called in order, on the publish, so they must be quick
+//!     (e.g., `TauriEmitter`, which sends the event to the frontend).
+//!   - The async listeners (`AsyncHubListener`) are spawned (tokio), for the backend work on the events
+//!     (e.g., the list subscription refresh, roll-ups, automations).
+//!   - The `target` of the event is only for the frontend (i.e., the windows `TauriEmitter` emits it to,
+//!     and the `events_since` of which windows return it).
 //!
 
+use crate::ctx::Ctx;
+use crate::window::ProjectWindows;
 use serde::Serialize;
+use serde_json::Value;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, Wry};
 use ts_rs::TS;
 
 #[derive(TS, Serialize, Clone)]
@@ -15,6 +32,276 @@ pub struct HubEvent<D: Serialize + Clone> {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub label: Option<String>,
 
+	/// Sequence number of the `Model` events (see `model::EventLog`), to catch up on the missed events.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[ts(type = "number")]
+	pub seq: Option<u64>,
+
+	/// The client request id of the call which fired the event (see `Ctx::request_id`), so the caller can
+	/// ignore the echoes of its own mutations.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub request_id: Option<String>,
+
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub data: Option<D>,
+
+	#[serde(skip)]
+	pub target: EventTarget,
+}
+
+/// The windows the event is emitted to (see `TauriEmitter`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum EventTarget {
+	#[default]
+	All,
+	/// The window of the call which fired the event (see `Ctx::window_label`), all windows when none (e.g., headless).
+	Origin,
+	/// The window of the label (e.g., of a list subscription).
+	Window(String),
+	/// The windows of the project id (see `window::ProjectWindows`), and the other windows (e.g., `main`),
+	/// but not the windows of the other projects.
+	Project(String),
+}
+
+impl EventTarget {
+	/// The target with the origin as the window of the ctx (i.e., never `Origin`).
+	pub fn resolve(&self, ctx: &Ctx) -> EventTarget {
+		match (self, ctx.window_label()) {
+			(EventTarget::Origin, Some(label)) => EventTarget::Window(label.to_string()),
+			(EventTarget::Origin, None) => EventTarget::All,
+			(target, _) => target.clone(),
+		}
+	}
+
+	/// If the event is emitted to the window of the label (`Origin` being resolved before, see `resolve`).
+	pub fn is_for_window(&self, label: &str, project_windows: &ProjectWindows) -> bool {
+		match self {
+			EventTarget::All | EventTarget::Origin => true,
+			EventTarget::Window(target_label) => target_label == label,
+			EventTarget::Project(project_id) => project_windows.is_for(label, project_id),
+		}
+	}
+}
+
+// region:    --- Listeners
+
+pub type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Listener called on each publish of the bus (must not block).
+pub trait HubListener: Send + Sync {
+	fn on_event(&self, ctx: &Arc<Ctx>, event: &HubEvent<Value>);
+}
+
+/// Listener spawned on each publish of the bus.
+pub trait AsyncHubListener: Send + Sync {
+	fn on_event(&self, ctx: Arc<Ctx>, event: Arc<HubEvent<Value>>) -> BoxFuture;
+}
+
+/// Emits the events to the windows of their target (i.e., the `HubEvent` Tauri event).
+pub struct TauriEmitter {
+	app_handle: AppHandle<Wry>,
+	project_windows: Arc<ProjectWindows>,
+}
+
+impl TauriEmitter {
+	pub fn new(app_handle: AppHandle<Wry>, project_windows: Arc<ProjectWindows>) -> Self {
+		TauriEmitter {
+			app_handle,
+			project_windows,
+		}
+	}
+}
+
+impl HubListener for TauriEmitter {
+	fn on_event(&self, ctx: &Arc<Ctx>, event: &HubEvent<Value>) {
+		let app = &self.app_handle;
+		let target = event.target.resolve(ctx);
+		let _ = match &target {
+			EventTarget::All | EventTarget::Origin => app.emit_all("HubEvent", event),
+			EventTarget::Window(label) => app.emit_to(label, "HubEvent", event),
+			EventTarget::Project(_) => app
+				.windows()
+				.keys()
+				.filter(|label| target.is_for_window(label, &self.project_windows))
+				.try_for_each(|label| app.emit_to(label, "HubEvent", event)),
+		};
+	}
+}
+
+// endregion: --- Listeners
+
+// region:    --- EventBus
+
+#[derive(Default)]
+pub struct EventBus {
+	listeners: Vec<Box<dyn HubListener>>,
+	async_listeners: Vec<Box<dyn AsyncHubListener>>,
+}
+
+impl EventBus {
+	pub fn with_listener(mut self, listener: impl HubListener + 'static) -> Self {
+		self.listeners.push(Box::new(listener));
+		self
+	}
+
+	pub fn with_async_listener(mut self, listener: impl AsyncHubListener + 'static) -> Self {
+		self.async_listeners.push(Box::new(listener));
+		self
+	}
+
+	/// Call the sync listeners, and spawn the async ones.
+	///
+	/// Note: Events with a data not serializable to JSON are not published (as they could not be emitted anyway),
+	///       but logged.
+	pub fn publish<D: Serialize + Clone>(&self, ctx: &Arc<Ctx>, hub_event: HubEvent<D>) {
+		let data = match hub_event.data.map(serde_json::to_value).transpose() {
+			Ok(data) => data,
+			Err(ex) => {
+				tracing::error!(
+					hub = %hub_event.hub,
+					topic = %hub_event.topic,
+					error = %ex,
+					"event data not serializable, event not published"
+				);
+				return;
+			}
+		};
+		let event = HubEvent {
+			hub: hub_event.hub,
+			topic: hub_event.topic,
+			label: hub_event.label,
+			seq: hub_event.seq,
+			request_id: hub_event.request_id,
+			data,
+			target: hub_event.target,
+		};
+
+		for listener in self.listeners.iter() {
+			listener.on_event(ctx, &event);
+		}
+
+		if !self.async_listeners.is_empty() {
+			let event = Arc::new(event);
+			for listener in self.async_listeners.iter() {
+				tokio::spawn(listener.on_event(ctx.clone(), event.clone()));
+			}
+		}
+	}
+}
+
+// endregion: --- EventBus
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_utils::new_test_ctx;
+	use parking_lot::Mutex;
+	use std::collections::HashMap;
+	use std::time::Duration;
+	use tokio::sync::{mpsc, Notify};
+
+	/// Records the topics of the events it is called with, prefixed with its name.
+	struct Recorder {
+		name: &'static str,
+		calls: Arc<Mutex<Vec<String>>>,
+	}
+
+	impl HubListener for Recorder {
+		fn on_event(&self, _ctx: &Arc<Ctx>, event: &HubEvent<Value>) {
+			self.calls
+				.lock()
+				.push(format!("{}:{}", self.name, event.topic));
+		}
+	}
+
+	/// Sends the topics of the events, once notified (i.e., after the publish returned).
+	struct Sender {
+		notify: Arc<Notify>,
+		tx: mpsc::UnboundedSender<String>,
+	}
+
+	impl AsyncHubListener for Sender {
+		fn on_event(&self, _ctx: Arc<Ctx>, event: Arc<HubEvent<Value>>) -> BoxFuture {
+			let notify = self.notify.clone();
+			let tx = self.tx.clone();
+			Box::pin(async move {
+				notify.notified().await;
+				let _ = tx.send(event.topic.clone());
+			})
+		}
+	}
+
+	fn event<D: Serialize + Clone>(topic: &str, data: D) -> HubEvent<D> {
+		HubEvent {
+			hub: "Test".to_string(),
+			topic: topic.to_string(),
+			label: None,
+			seq: None,
+			request_id: None,
+			data: Some(data),
+			target: EventTarget::All,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_event_target_resolve() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, _events) = new_test_ctx().await?;
+		let window_ctx = ctx.with_window_label("main");
+		let project = EventTarget::Project("project:a".to_string());
+
+		// --- EXEC & CHECK
+		assert_eq!(
+			EventTarget::Origin.resolve(&window_ctx),
+			EventTarget::Window("main".to_string())
+		);
+		assert_eq!(EventTarget::Origin.resolve(&ctx), EventTarget::All);
+		assert_eq!(project.resolve(&window_ctx), project);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_event_bus_publish() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (app_ctx, _events) = new_test_ctx().await?;
+		let calls = Arc::new(Mutex::new(Vec::new()));
+		let notify = Arc::new(Notify::new());
+		let (tx, mut rx) = mpsc::unbounded_channel();
+		let event_bus = EventBus::default()
+			.with_listener(Recorder {
+				name: "a",
+				calls: calls.clone(),
+			})
+			.with_async_listener(Sender {
+				notify: notify.clone(),
+				tx,
+			})
+			.with_listener(Recorder {
+				name: "b",
+				calls: calls.clone(),
+			});
+		let ctx = Arc::new(Ctx::new(app_ctx.get_model_manager(), Arc::new(event_bus)));
+
+		// --- EXEC
+		ctx.emit_hub_event(event("topic_01", "data"));
+
+		// --- CHECK - sync listeners called in order, on the publish
+		assert_eq!(*calls.lock(), vec!["a:topic_01", "b:topic_01"]);
+
+		// --- CHECK - async listener spawned (still waiting, then runs)
+		assert!(rx.try_recv().is_err());
+		notify.notify_one();
+		let topic = tokio::time::timeout(Duration::from_secs(1), rx.recv()).await?;
+		assert_eq!(topic.as_deref(), Some("topic_01"));
+
+		// --- EXEC & CHECK - data not serializable, not published
+		let data: HashMap<(u8, u8), u8> = [((1, 2), 3)].into_iter().collect();
+		ctx.emit_hub_event(event("topic_02", data));
+		assert_eq!(calls.lock().len(), 2);
+
+		Ok(())
+	}
 }
+// endregion: --- Tests
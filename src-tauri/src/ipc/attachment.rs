@@ -0,0 +1,69 @@
+//! Tauri IPC commands to bridge Attachment Frontend Model Controller to Backend Model Controller
+//!
+
+use crate::ctx::Ctx;
+use crate::ipc::{CreateParams, DeleteParams, GetParams, IpcResponse, ListParams, ParamsSummary};
+use crate::model::{Attachment, AttachmentBmc, AttachmentForCreate, ModelMutateResultData};
+use crate::Error;
+use serde_json::Value;
+use tauri::{command, Window, Wry};
+use tracing::instrument;
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn get_attachment(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: GetParams,
+) -> IpcResponse<Attachment> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => AttachmentBmc::get(ctx, &params.id).await.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+/// Note: The data is deserialized here (rather than by Tauri) to return an IpcError on a non task id.
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn create_attachment(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: CreateParams<Value>,
+) -> IpcResponse<ModelMutateResultData> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => match serde_json::from_value::<AttachmentForCreate>(params.data) {
+			Ok(val) => AttachmentBmc::create(ctx, val).await.into(),
+			Err(err) => Err(Error::JsonSerde(err)).into(),
+		},
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn delete_attachment(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: DeleteParams,
+) -> IpcResponse<ModelMutateResultData> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => AttachmentBmc::delete(ctx, &params.id).await.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn list_attachments(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: ListParams<Value>,
+) -> IpcResponse<Vec<Attachment>> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => match params.filter.map(serde_json::from_value).transpose() {
+			Ok(filter) => AttachmentBmc::list(ctx, filter).await.into(),
+			Err(err) => Err(Error::JsonSerde(err)).into(),
+		},
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
@@ -0,0 +1,88 @@
+//! Tauri IPC commands to bridge Comment Frontend Model Controller to Backend Model Controller
+//!
+
+use crate::ctx::Ctx;
+use crate::ipc::{
+	CreateParams, DeleteParams, GetParams, IpcResponse, ListParams, ParamsSummary, UpdateParams,
+};
+use crate::model::{
+	Comment, CommentBmc, CommentForCreate, CommentForUpdate, ModelMutateResultData,
+};
+use crate::Error;
+use serde_json::Value;
+use tauri::{command, Window, Wry};
+use tracing::instrument;
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn get_comment(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: GetParams,
+) -> IpcResponse<Comment> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => CommentBmc::get(ctx, &params.id).await.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+/// Note: The data is deserialized here (rather than by Tauri) to return an IpcError on a non task id.
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn create_comment(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: CreateParams<Value>,
+) -> IpcResponse<ModelMutateResultData> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => match serde_json::from_value::<CommentForCreate>(params.data) {
+			Ok(val) => CommentBmc::create(ctx, val).await.into(),
+			Err(err) => Err(Error::JsonSerde(err)).into(),
+		},
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn update_comment(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: UpdateParams<CommentForUpdate>,
+) -> IpcResponse<ModelMutateResultData> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => CommentBmc::update(ctx, &params.id, params.data)
+			.await
+			.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn delete_comment(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: DeleteParams,
+) -> IpcResponse<ModelMutateResultData> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => CommentBmc::delete(ctx, &params.id).await.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn list_comments(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: ListParams<Value>,
+) -> IpcResponse<Vec<Comment>> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => match params.filter.map(serde_json::from_value).transpose() {
+			Ok(filter) => CommentBmc::list(ctx, filter).await.into(),
+			Err(err) => Err(Error::JsonSerde(err)).into(),
+		},
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
@@ -0,0 +1,233 @@
+//! The error sent back to the frontend (e.g., `IpcResponse.error`).
+//!
+//! Each application `Error` variant is mapped to a stable `IpcErrorCode`, so the UI can branch on the code
+//! rather than on the message (which is for humans, and can change).
+//!
+//! Notes:
+//!   - `field` is the path of the invalid property in the params (e.g., `data.due_date`), when known.
+//!   - `details` carries the error data (e.g., the blocker ids of `TaskBlocked`).
+
+use crate::prelude::*;
+use crate::Error;
+use serde::Serialize;
+use serde_json::{json, Value};
+use serde_with_macros::skip_serializing_none;
+use ts_rs::TS;
+
+#[derive(Serialize, TS, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub enum IpcErrorCode {
+	/// The entity (or record) does not exist.
+	NotFound,
+	/// The params or data are invalid (e.g., wrong date format).
+	Validation,
+	/// The operation is not allowed in the current state (e.g., task blocked, project archived).
+	Conflict,
+	/// The store (or file store) failed.
+	Store,
+	/// Application error, not caused by the request.
+	Internal,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, TS, Debug)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct IpcError {
+	pub code: IpcErrorCode,
+	pub message: String,
+	pub field: Option<String>,
+	#[ts(type = "any")]
+	pub details: Option<Value>,
+}
+
+impl IpcError {
+	fn new(code: IpcErrorCode, message: impl Into<String>) -> Self {
+		IpcError {
+			code,
+			message: message.into(),
+			field: None,
+			details: None,
+		}
+	}
+
+	fn with_field(mut self, field: &str) -> Self {
+		self.field = Some(field.to_string());
+		self
+	}
+
+	fn with_details(mut self, details: Value) -> Self {
+		self.details = Some(details);
+		self
+	}
+}
+
+impl From<Error> for IpcError {
+	fn from(err: Error) -> Self {
+		use IpcErrorCode::*;
+
+		match err {
+			// -- Not found
+			Error::StoreRecordNotFound(id) => {
+				IpcError::new(NotFound, f!("Not found: {id}")).with_details(json!({ "id": id }))
+			}
+			Error::WindowNotFound(label) => IpcError::new(NotFound, f!("Window not open: {label}"))
+				.with_details(json!({ "label": label })),
+
+			// -- Validation
+			Error::Validation(violations) => {
+				let message = violations
+					.iter()
+					.map(|v| f!("{}: {}", v.field, v.message))
+					.collect::<Vec<_>>()
+					.join(", ");
+				let field = violations.first().map(|v| f!("data.{}", v.field));
+				IpcError {
+					code: Validation,
+					message: f!("Invalid data - {message}"),
+					field,
+					details: Some(json!({ "violations": violations })),
+				}
+			}
+			Error::JsonSerde(ex) => IpcError::new(Validation, f!("Invalid params: {ex}")),
+			Error::Modql(ex) => {
+				IpcError::new(Validation, f!("Invalid filter: {ex:?}")).with_field("filter")
+			}
+			Error::ModqlOperatorNotSupported(op) => {
+				IpcError::new(Validation, f!("Filter operator not supported: {op}"))
+					.with_field("filter")
+			}
+			Error::StoreDbNameInvalid(db) => {
+				IpcError::new(Validation, f!("Invalid database name: {db}"))
+			}
+			Error::RecurRuleInvalid(rule) => {
+				IpcError::new(Validation, f!("Invalid recurrence rule: {rule}"))
+					.with_field("data.recur_rule")
+			}
+			Error::DateInvalid(date) => {
+				IpcError::new(Validation, f!("Invalid date (expected YYYY-MM-DD): {date}"))
+			}
+			Error::TaskDepInvalid(msg) => IpcError::new(Validation, msg),
+			Error::StoreIdNotOfTable(id) => {
+				IpcError::new(Validation, f!("Id not of the expected entity: {id}"))
+					.with_field("id")
+			}
+			Error::ProjectNotTemplate(id) => {
+				IpcError::new(Validation, f!("Project is not a template: {id}")).with_field("id")
+			}
+			Error::SubscriptionEntityNotSupported(entity) => IpcError::new(
+				Validation,
+				f!("Entity not supported for subscription: {entity}"),
+			)
+			.with_field("entity"),
+			Error::AttachmentSourceNotFile(path) => {
+				IpcError::new(Validation, f!("Not a file: {path}")).with_field("data.file_path")
+			}
+
+			// -- Conflict
+			Error::TaskDepCycle(path) => {
+				IpcError::new(Conflict, "The dependency would create a cycle")
+					.with_details(json!({ "path": path }))
+			}
+			Error::TaskBlocked(blocker_ids) => {
+				IpcError::new(Conflict, "The task is blocked by open tasks")
+					.with_details(json!({ "blocker_ids": blocker_ids }))
+			}
+			Error::ProjectArchived(id) => {
+				IpcError::new(Conflict, "The project is archived (tasks are read-only)")
+					.with_details(json!({ "project_id": id }))
+			}
+			Error::WorkspaceActive(id) => {
+				IpcError::new(Conflict, "The active workspace cannot be deleted")
+					.with_details(json!({ "workspace_id": id }))
+			}
+			Error::TimerAlreadyRunning(id) => IpcError::new(Conflict, "A timer is already running")
+				.with_details(json!({ "time_entry_id": id })),
+			Error::TimerNotRunning => IpcError::new(Conflict, "No timer is running"),
+
+			// -- Store
+			Error::XValueNotOfType(_)
+			| Error::XPropertyNotFound(_)
+			| Error::XValueSerde(_)
+			| Error::StoreFailToCreate(_)
+			| Error::AttachmentFailToStore(_)
+			| Error::Surreal(_) => IpcError::new(Store, f!("Store error: {err}")),
+
+			// -- Internal
+			Error::CtxFail
+			| Error::AppDataDirNotFound
+			| Error::AppConfigDirNotFound
+			| Error::AppLogDirNotFound
+			| Error::ConfigFileRead(_)
+			| Error::ConfigParse(_)
+			| Error::ConfigInvalid(_)
+			| Error::Tauri(_)
+			| Error::IO(_) => IpcError::new(Internal, f!("Internal error: {err}")),
+		}
+	}
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::{IpcError, IpcErrorCode};
+	use crate::model::{FieldViolation, TaskDepForCreate};
+	use crate::Error;
+	use serde_json::json;
+
+	#[test]
+	fn test_ipc_error_from_error() -> anyhow::Result<()> {
+		// --- EXEC
+		let blocked = IpcError::from(Error::TaskBlocked(vec!["task:1".to_string()]));
+		let not_found = IpcError::from(Error::StoreRecordNotFound("task:2".to_string()));
+		let rule = IpcError::from(Error::RecurRuleInvalid("FREQ=NEVER".to_string()));
+
+		// --- CHECK
+		assert_eq!(
+			serde_json::to_value(&blocked)?,
+			json!({
+				"code": "CONFLICT",
+				"message": "The task is blocked by open tasks",
+				"details": {"blocker_ids": ["task:1"]}
+			})
+		);
+		assert_eq!(not_found.code, IpcErrorCode::NotFound);
+		assert_eq!(rule.code, IpcErrorCode::Validation);
+		assert_eq!(rule.field.as_deref(), Some("data.recur_rule"));
+
+		// --- EXEC & CHECK - validation
+		let violation = FieldViolation {
+			field: "name".to_string(),
+			message: "is required".to_string(),
+		};
+		let validation = IpcError::from(Error::Validation(vec![violation]));
+		assert_eq!(validation.message, "Invalid data - name: is required");
+		assert_eq!(validation.field.as_deref(), Some("data.name"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_ipc_error_from_id_not_of_table() -> anyhow::Result<()> {
+		// --- FIXTURE
+		// As converted by the commands from the params `Value` (e.g., `create_task_dep`).
+		let data = json!({"task_id": "task:1", "blocker_id": "project:2"});
+
+		// --- EXEC
+		let err = match serde_json::from_value::<TaskDepForCreate>(data) {
+			Ok(_) => panic!("a project id should not be a task id"),
+			Err(ex) => IpcError::from(Error::JsonSerde(ex)),
+		};
+
+		// --- CHECK
+		assert_eq!(err.code, IpcErrorCode::Validation);
+		assert!(
+			err.message.contains("not a task id: project:2"),
+			"{}",
+			err.message
+		);
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -0,0 +1,29 @@
+//! Tauri IPC command to catch up on the missed `Model` events (see `model::event_log`).
+//!
+
+use crate::ctx::Ctx;
+use crate::event::HubEvent;
+use crate::ipc::{IpcResponse, ParamsSummary, SeqParams};
+use crate::model::{EventLogBmc, EventsSinceData};
+use crate::window::ProjectWindows;
+use crate::Error;
+use serde_json::Value;
+use std::sync::Arc;
+use tauri::{command, Manager, Window, Wry};
+use tracing::instrument;
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn events_since(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: SeqParams,
+) -> IpcResponse<EventsSinceData<HubEvent<Value>>> {
+	let project_windows = (*window.state::<Arc<ProjectWindows>>()).clone();
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => EventLogBmc::events_since(ctx, params.seq, &project_windows)
+			.await
+			.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
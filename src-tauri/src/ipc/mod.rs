@@ -9,15 +9,42 @@
 //! JSON-RPC 2.0 cloud backend easily.
 //!
 //! Notes:
+//!   - The `rpc` command (see `rpc.rs`) is a single JSON-RPC 2.0 entry point (with request ids and batching)
+//!     for the project and task methods.
+//!   - The commands also take an optional `request_id` argument (i.e., `requestId` in the invoke args), set by the
+//!     caller, and put on the `Model` events of the call (see `Ctx::request_id`), to recognize its own mutations.
+//!   - Each command runs in its tracing span (named after the command), with the `params` summary (see `ParamsSummary`),
+//!     the `request_id`, and the `outcome` (`ok` or the `IpcErrorCode`, recorded by `IpcResponse`).
+//!     The duration is logged on the span close (see `logging`).
 //!   - This module re-exports the appropriate sub-module constructs as their hierarchy is irrelevant to callers.
 
+mod attachment;
+mod comment;
+mod error;
+mod event_log;
 mod params;
 mod project;
 mod response;
+mod rpc;
+mod subscription;
 mod task;
+mod task_dep;
+mod time_entry;
+mod window;
+mod workspace;
 
 // --- re-exports
+pub use attachment::*;
+pub use comment::*;
+pub use error::*;
+pub use event_log::*;
 pub use params::*;
 pub use project::*;
 pub use response::*;
+pub use rpc::*;
+pub use subscription::*;
 pub use task::*;
+pub use task_dep::*;
+pub use time_entry::*;
+pub use window::*;
+pub use workspace::*;
@@ -2,31 +2,199 @@
 //!
 //! The current best practice is to follow a single argument type, called "params" for all method (JSON-RPC's style).
 //!
+//! The `I` id type parameter is for the typed ids (e.g., `GetParams<ProjectId>`), checked on deserialization.
+//!
+//! The `ParamsSummary` of the params is recorded on the IPC command span (see `ipc::mod`).
+//!
 
+use crate::prelude::*;
 use serde::Deserialize;
+use std::fmt::Display;
 
 #[derive(Deserialize)]
 pub struct CreateParams<D> {
 	pub data: D,
 }
 
+/// For the non CRUD commands taking some data (e.g., `start_timer`).
+#[derive(Deserialize)]
+pub struct DataParams<D> {
+	pub data: D,
+}
+
+/// For the non CRUD commands taking an id and some data (e.g., `clone_project`).
+#[derive(Deserialize)]
+pub struct IdDataParams<D, I = String> {
+	pub id: I,
+	pub data: D,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateParams<D, I = String> {
+	pub id: I,
+	pub data: D,
+}
+
+/// For the bulk update commands (e.g., `update_tasks`), `target` being the filter or the ids of the entities.
 #[derive(Deserialize)]
-pub struct UpdateParams<D> {
-	pub id: String,
+pub struct BulkUpdateParams<T, D> {
+	pub target: T,
 	pub data: D,
 }
 
+/// For the bulk delete commands (e.g., `delete_tasks`).
+#[derive(Deserialize)]
+pub struct BulkDeleteParams<T> {
+	pub target: T,
+}
+
 #[derive(Deserialize)]
 pub struct ListParams<F> {
 	pub filter: Option<F>,
 }
 
+/// For the `subscribe_list` command, `filter` being the filter of the entity list (e.g., `TaskFilter`).
+#[derive(Deserialize)]
+pub struct SubscribeListParams<F> {
+	pub entity: String,
+	pub filter: Option<F>,
+}
+
+/// For the `events_since` command, `seq` being the last event seq received.
+#[derive(Deserialize)]
+pub struct SeqParams {
+	pub seq: u64,
+}
+
 #[derive(Deserialize)]
-pub struct GetParams {
-	pub id: String,
+pub struct GetParams<I = String> {
+	pub id: I,
 }
 
+/// For the non CRUD commands only taking an id (e.g., `archive_project`).
 #[derive(Deserialize)]
-pub struct DeleteParams {
-	pub id: String,
+pub struct IdParams<I = String> {
+	pub id: I,
+}
+
+#[derive(Deserialize)]
+pub struct DeleteParams<I = String> {
+	pub id: I,
+}
+
+// region:    --- ParamsSummary
+
+/// Short description of the params for the IPC command spans (e.g., `id=task:..`), never the data itself
+/// (e.g., the content of a comment).
+pub trait ParamsSummary {
+	fn summary(&self) -> String;
+}
+
+impl<D> ParamsSummary for CreateParams<D> {
+	fn summary(&self) -> String {
+		f!("data={}", type_short_name::<D>())
+	}
+}
+
+impl<D> ParamsSummary for DataParams<D> {
+	fn summary(&self) -> String {
+		f!("data={}", type_short_name::<D>())
+	}
+}
+
+impl<D, I: Display> ParamsSummary for IdDataParams<D, I> {
+	fn summary(&self) -> String {
+		f!("id={} data={}", self.id, type_short_name::<D>())
+	}
+}
+
+impl<D, I: Display> ParamsSummary for UpdateParams<D, I> {
+	fn summary(&self) -> String {
+		f!("id={} data={}", self.id, type_short_name::<D>())
+	}
+}
+
+impl<T, D> ParamsSummary for BulkUpdateParams<T, D> {
+	fn summary(&self) -> String {
+		f!("target data={}", type_short_name::<D>())
+	}
+}
+
+impl<T> ParamsSummary for BulkDeleteParams<T> {
+	fn summary(&self) -> String {
+		"target".to_string()
+	}
+}
+
+impl<F> ParamsSummary for ListParams<F> {
+	fn summary(&self) -> String {
+		match self.filter {
+			Some(_) => "filter".to_string(),
+			None => String::new(),
+		}
+	}
+}
+
+impl<F> ParamsSummary for SubscribeListParams<F> {
+	fn summary(&self) -> String {
+		f!("entity={}", self.entity)
+	}
+}
+
+impl ParamsSummary for SeqParams {
+	fn summary(&self) -> String {
+		f!("seq={}", self.seq)
+	}
+}
+
+impl<I: Display> ParamsSummary for GetParams<I> {
+	fn summary(&self) -> String {
+		f!("id={}", self.id)
+	}
+}
+
+impl<I: Display> ParamsSummary for IdParams<I> {
+	fn summary(&self) -> String {
+		f!("id={}", self.id)
+	}
+}
+
+impl<I: Display> ParamsSummary for DeleteParams<I> {
+	fn summary(&self) -> String {
+		f!("id={}", self.id)
+	}
+}
+
+/// The type name without its path (e.g., `TaskForCreate`).
+fn type_short_name<T>() -> &'static str {
+	let name = std::any::type_name::<T>();
+	name.rsplit("::").next().unwrap_or(name)
+}
+
+// endregion: --- ParamsSummary
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::{TaskForCreate, TaskId};
+	use serde_json::json;
+
+	#[test]
+	fn test_params_summary() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let get: GetParams<TaskId> = serde_json::from_value(json!({"id": "task:1"}))?;
+		let create: CreateParams<TaskForCreate> = serde_json::from_value(json!({
+			"data": {"project_id": "project:1", "title": "Secret title"}
+		}))?;
+		let list: ListParams<serde_json::Value> = serde_json::from_value(json!({}))?;
+
+		// --- EXEC & CHECK
+		assert_eq!(get.summary(), "id=task:1");
+		assert_eq!(create.summary(), "data=TaskForCreate", "not the data");
+		assert_eq!(list.summary(), "");
+
+		Ok(())
+	}
 }
+// endregion: --- Tests
@@ -1,66 +1,81 @@
 //! Tauri IPC commands to bridge Project Frontend Model Controller to Backend Model Controller
 //!
 
-use super::{CreateParams, DeleteParams, GetParams, IpcResponse, ListParams, UpdateParams};
+use super::{IdDataParams, IdParams, IpcResponse, ParamsSummary};
 use crate::ctx::Ctx;
-use crate::model::{
-	ModelMutateResultData, Project, ProjectBmc, ProjectForCreate, ProjectForUpdate,
-};
+use crate::model::{ModelMutateResultData, ProjectBmc, ProjectForClone, ProjectId};
 use crate::Error;
 use serde_json::Value;
-use tauri::{command, AppHandle, Wry};
+use tauri::{command, Window, Wry};
+use tracing::instrument;
 
-#[command]
-pub async fn get_project(app: AppHandle<Wry>, params: GetParams) -> IpcResponse<Project> {
-	match Ctx::from_app(app) {
-		Ok(ctx) => ProjectBmc::get(ctx, &params.id).await.into(),
-		Err(_) => Err(Error::CtxFail).into(),
-	}
-}
+// The CRUD commands (`get_project`, ..., `list_projects`) are generated by the `ProjectBmc` derive.
+pub use crate::model::project_commands::*;
+
+// Note: The ids are deserialized here (rather than by Tauri) to return an IpcError on a non project id.
 
 #[command]
-pub async fn create_project(
-	app: AppHandle<Wry>,
-	params: CreateParams<ProjectForCreate>,
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn archive_project(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: IdParams<Value>,
 ) -> IpcResponse<ModelMutateResultData> {
-	match Ctx::from_app(app) {
-		Ok(ctx) => ProjectBmc::create(ctx, params.data).await.into(),
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => match serde_json::from_value::<ProjectId>(params.id) {
+			Ok(val) => ProjectBmc::archive(ctx, &val).await.into(),
+			Err(err) => Err(Error::JsonSerde(err)).into(),
+		},
 		Err(_) => Err(Error::CtxFail).into(),
 	}
 }
 
 #[command]
-pub async fn update_project(
-	app: AppHandle<Wry>,
-	params: UpdateParams<ProjectForUpdate>,
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn unarchive_project(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: IdParams<Value>,
 ) -> IpcResponse<ModelMutateResultData> {
-	match Ctx::from_app(app) {
-		Ok(ctx) => ProjectBmc::update(ctx, &params.id, params.data)
-			.await
-			.into(),
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => match serde_json::from_value::<ProjectId>(params.id) {
+			Ok(val) => ProjectBmc::unarchive(ctx, &val).await.into(),
+			Err(err) => Err(Error::JsonSerde(err)).into(),
+		},
 		Err(_) => Err(Error::CtxFail).into(),
 	}
 }
 
 #[command]
-pub async fn delete_project(
-	app: AppHandle<Wry>,
-	params: DeleteParams,
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn clone_project(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: IdDataParams<ProjectForClone, Value>,
 ) -> IpcResponse<ModelMutateResultData> {
-	match Ctx::from_app(app) {
-		Ok(ctx) => ProjectBmc::delete(ctx, &params.id).await.into(),
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => match serde_json::from_value::<ProjectId>(params.id) {
+			Ok(val) => ProjectBmc::clone_project(ctx, &val, params.data)
+				.await
+				.into(),
+			Err(err) => Err(Error::JsonSerde(err)).into(),
+		},
 		Err(_) => Err(Error::CtxFail).into(),
 	}
 }
 
 #[command]
-pub async fn list_projects(
-	app: AppHandle<Wry>,
-	params: ListParams<Value>,
-) -> IpcResponse<Vec<Project>> {
-	match Ctx::from_app(app) {
-		Ok(ctx) => match params.filter.map(serde_json::from_value).transpose() {
-			Ok(filter) => ProjectBmc::list(ctx, filter).await.into(),
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn create_project_from_template(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: IdDataParams<ProjectForClone, Value>,
+) -> IpcResponse<ModelMutateResultData> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => match serde_json::from_value::<ProjectId>(params.id) {
+			Ok(val) => ProjectBmc::create_from_template(ctx, &val, params.data)
+				.await
+				.into(),
 			Err(err) => Err(Error::JsonSerde(err)).into(),
 		},
 		Err(_) => Err(Error::CtxFail).into(),
@@ -1,18 +1,15 @@
 //! Here we are following a "JSON-RPC 2.0" styleesponse with error or result.
 //!
 //! Notes:
-//!     - For now, we do not handle the "request.id" of "JSON-RPC 2.0", and request batching
-//!       but this could be added later.
+//!     - The per method commands do not handle the "request.id" of "JSON-RPC 2.0", and request batching.
+//!       The `rpc` command (see `rpc.rs`) does, with spec compliant responses.
 //!     - The benefit of following the "JSON-RPC 2.0" scheme is that the frontend could be adapted to talk to a
 //!       web server with minimum effort, and the JSON-RPC data format for request/response is simple, clean, and well thought out.
 
+use super::IpcError;
 use crate::Result;
 use serde::Serialize;
-
-#[derive(Serialize)]
-struct IpcError {
-	message: String,
-}
+use tracing::Span;
 
 #[derive(Serialize)]
 pub struct IpcSimpleResult<D>
@@ -36,17 +33,25 @@ where
 	D: Serialize,
 {
 	fn from(res: Result<D>) -> Self {
+		// Note: Recorded on the span of the IPC command (see `ipc::mod`), when called in it.
 		match res {
-			Ok(data) => IpcResponse {
-				error: None,
-				result: Some(IpcSimpleResult { data }),
-			},
-			Err(err) => IpcResponse {
-				error: Some(IpcError {
-					message: format!("{err}"),
-				}),
-				result: None,
-			},
+			Ok(data) => {
+				Span::current().record("outcome", "ok");
+				IpcResponse {
+					error: None,
+					result: Some(IpcSimpleResult { data }),
+				}
+			}
+			Err(err) => {
+				let error = IpcError::from(err);
+				// Note: Only the code, as the error messages can have user data (e.g., the invalid values).
+				tracing::warn!(code = ?error.code, "ipc error");
+				Span::current().record("outcome", tracing::field::debug(&error.code));
+				IpcResponse {
+					error: Some(error),
+					result: None,
+				}
+			}
 		}
 	}
 }
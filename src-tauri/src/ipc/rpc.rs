@@ -0,0 +1,469 @@
+//! Single `rpc` IPC entry point following the "JSON-RPC 2.0" spec (https://www.jsonrpc.org/specification).
+//!
+//! The `params` of the `rpc` command is a request object (`{jsonrpc: "2.0", id, method, params}`)
+//! or a batch (array) of request objects.
+//!
+//! Notes:
+//!   - The methods are the names of the project and task commands (e.g., `list_tasks`),
+//!     with the same params (the params of the command).
+//!   - Requests without `id` are notifications, they are executed but do not get a response.
+//!     When nothing has to be returned (e.g., batch of notifications), the command returns `null`.
+//!   - The `id` must be a string, a number, or `null` (e.g., an object id is an `Invalid Request`).
+//!   - Batch requests are executed sequentially, in order.
+
+use super::{
+	BulkDeleteParams, BulkUpdateParams, CreateParams, DeleteParams, GetParams, IdDataParams,
+	IdParams, IpcError, ListParams, UpdateParams,
+};
+use crate::ctx::Ctx;
+use crate::model::{
+	ProjectBmc, ProjectFilter, ProjectForClone, ProjectForCreate, ProjectForUpdate, ProjectId,
+	TaskBmc, TaskFilter, TaskForCreate, TaskForUpdate, TaskId, TaskTarget,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+use tauri::{command, Window, Wry};
+use tracing::{info_span, instrument, Instrument};
+
+// region:    --- Rpc Types
+
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+/// Application (i.e., model) errors, in the spec "server error" range.
+const APP_ERROR: i64 = -32000;
+
+#[derive(Serialize, Debug)]
+pub struct RpcError {
+	code: i64,
+	message: String,
+	/// The application error (for the `APP_ERROR` code)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	data: Option<Box<IpcError>>,
+}
+
+impl RpcError {
+	fn new(code: i64, message: impl Into<String>) -> Self {
+		RpcError {
+			code,
+			message: message.into(),
+			data: None,
+		}
+	}
+}
+
+impl From<IpcError> for RpcError {
+	fn from(ipc_error: IpcError) -> Self {
+		RpcError {
+			code: APP_ERROR,
+			message: ipc_error.message.clone(),
+			data: Some(Box::new(ipc_error)),
+		}
+	}
+}
+
+#[derive(Serialize, Debug)]
+pub struct RpcResponse {
+	jsonrpc: &'static str,
+	/// `null` when the request id could not be read (e.g., invalid request)
+	id: Value,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	result: Option<Value>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	error: Option<RpcError>,
+}
+
+impl RpcResponse {
+	fn new(id: Value, res: Result<Value, RpcError>) -> Self {
+		let (result, error) = match res {
+			Ok(result) => (Some(result), None),
+			Err(error) => (None, Some(error)),
+		};
+		RpcResponse {
+			jsonrpc: "2.0",
+			id,
+			result,
+			error,
+		}
+	}
+}
+
+#[derive(Serialize, Debug)]
+#[serde(untagged)]
+pub enum RpcOutput {
+	Single(RpcResponse),
+	Batch(Vec<RpcResponse>),
+	/// Only notifications (serialized as `null`)
+	Empty,
+}
+
+// endregion: --- Rpc Types
+
+#[command]
+#[instrument(skip_all, fields(request_id = request_id.as_deref()))]
+pub async fn rpc(window: Window<Wry>, request_id: Option<String>, params: Value) -> RpcOutput {
+	let ctx = match Ctx::from_window(window, request_id) {
+		Ok(ctx) => ctx,
+		Err(_) => {
+			let error = RpcError::new(INTERNAL_ERROR, "CtxFail");
+			return RpcOutput::Single(RpcResponse::new(Value::Null, Err(error)));
+		}
+	};
+
+	exec_rpc(ctx, params).await
+}
+
+async fn exec_rpc(ctx: Arc<Ctx>, params: Value) -> RpcOutput {
+	match params {
+		Value::Array(requests) if requests.is_empty() => RpcOutput::Single(RpcResponse::new(
+			Value::Null,
+			Err(RpcError::new(INVALID_REQUEST, "Empty batch")),
+		)),
+		Value::Array(requests) => {
+			let mut responses = Vec::new();
+			for request in requests {
+				if let Some(response) = exec_request(ctx.clone(), request).await {
+					responses.push(response);
+				}
+			}
+			if responses.is_empty() {
+				RpcOutput::Empty
+			} else {
+				RpcOutput::Batch(responses)
+			}
+		}
+		request => match exec_request(ctx, request).await {
+			Some(response) => RpcOutput::Single(response),
+			None => RpcOutput::Empty,
+		},
+	}
+}
+
+/// Execute one request. Returns None for the (valid) notifications.
+async fn exec_request(ctx: Arc<Ctx>, request: Value) -> Option<RpcResponse> {
+	let request = match parse_request(request) {
+		Ok(request) => request,
+		Err(response) => return Some(response),
+	};
+
+	// Note: One span per request of the batch, the `outcome` being `ok` or the JSON-RPC error code.
+	let span = info_span!("rpc_request", method = %request.method, outcome = tracing::field::Empty);
+	let res = dispatch(ctx, &request.method, request.params)
+		.instrument(span.clone())
+		.await;
+	match &res {
+		Ok(_) => span.record("outcome", "ok"),
+		Err(err) => span.record("outcome", err.code),
+	};
+
+	request.id.map(|id| RpcResponse::new(id, res))
+}
+
+struct RpcRequest {
+	/// None for notifications. Note: `"id": null` is a request (not a notification).
+	id: Option<Value>,
+	method: String,
+	params: Value,
+}
+
+/// Validate a request, or returns its `Invalid Request` error response.
+fn parse_request(request: Value) -> Result<RpcRequest, RpcResponse> {
+	let mut request = match request {
+		Value::Object(request) => request,
+		_ => {
+			let error = RpcError::new(INVALID_REQUEST, "Request must be an object");
+			return Err(RpcResponse::new(Value::Null, Err(error)));
+		}
+	};
+
+	let id = match request.remove("id") {
+		Some(Value::Array(_) | Value::Object(_) | Value::Bool(_)) => {
+			let error = RpcError::new(
+				INVALID_REQUEST,
+				"Request `id` must be a string, a number, or null",
+			);
+			return Err(RpcResponse::new(Value::Null, Err(error)));
+		}
+		id => id,
+	};
+	let method = match (request.remove("jsonrpc"), request.remove("method")) {
+		(Some(Value::String(v)), Some(Value::String(method))) if v == "2.0" => method,
+		_ => {
+			let error = RpcError::new(
+				INVALID_REQUEST,
+				"Request must have `jsonrpc: \"2.0\"` and a string `method`",
+			);
+			return Err(RpcResponse::new(id.unwrap_or(Value::Null), Err(error)));
+		}
+	};
+	// Omitted params are handled as empty (e.g., `list_projects` without filter).
+	let params = request
+		.remove("params")
+		.unwrap_or_else(|| Value::Object(Default::default()));
+
+	Ok(RpcRequest { id, method, params })
+}
+
+// region:    --- Dispatch
+
+async fn dispatch(ctx: Arc<Ctx>, method: &str, params: Value) -> Result<Value, RpcError> {
+	match method {
+		// -- Project
+		"get_project" => {
+			let params: GetParams<ProjectId> = parse_params(params)?;
+			into_result(ProjectBmc::get(ctx, &params.id).await)
+		}
+		"create_project" => {
+			let params: CreateParams<ProjectForCreate> = parse_params(params)?;
+			into_result(ProjectBmc::create(ctx, params.data).await)
+		}
+		"update_project" => {
+			let params: UpdateParams<ProjectForUpdate, ProjectId> = parse_params(params)?;
+			into_result(ProjectBmc::update(ctx, &params.id, params.data).await)
+		}
+		"delete_project" => {
+			let params: DeleteParams<ProjectId> = parse_params(params)?;
+			into_result(ProjectBmc::delete(ctx, &params.id).await)
+		}
+		"list_projects" => {
+			let params: ListParams<ProjectFilter> = parse_params(params)?;
+			into_result(ProjectBmc::list(ctx, params.filter).await)
+		}
+		"archive_project" => {
+			let params: IdParams<ProjectId> = parse_params(params)?;
+			into_result(ProjectBmc::archive(ctx, &params.id).await)
+		}
+		"unarchive_project" => {
+			let params: IdParams<ProjectId> = parse_params(params)?;
+			into_result(ProjectBmc::unarchive(ctx, &params.id).await)
+		}
+		"clone_project" => {
+			let params: IdDataParams<ProjectForClone, ProjectId> = parse_params(params)?;
+			into_result(ProjectBmc::clone_project(ctx, &params.id, params.data).await)
+		}
+		"create_project_from_template" => {
+			let params: IdDataParams<ProjectForClone, ProjectId> = parse_params(params)?;
+			into_result(ProjectBmc::create_from_template(ctx, &params.id, params.data).await)
+		}
+
+		// -- Task
+		"get_task" => {
+			let params: GetParams<TaskId> = parse_params(params)?;
+			into_result(TaskBmc::get(ctx, &params.id).await)
+		}
+		"create_task" => {
+			let params: CreateParams<TaskForCreate> = parse_params(params)?;
+			into_result(TaskBmc::create(ctx, params.data).await)
+		}
+		"update_task" => {
+			let params: UpdateParams<TaskForUpdate, TaskId> = parse_params(params)?;
+			into_result(TaskBmc::update(ctx, &params.id, params.data).await)
+		}
+		"delete_task" => {
+			let params: DeleteParams<TaskId> = parse_params(params)?;
+			into_result(TaskBmc::delete(ctx, &params.id).await)
+		}
+		"list_tasks" => {
+			let params: ListParams<TaskFilter> = parse_params(params)?;
+			into_result(TaskBmc::list(ctx, params.filter).await)
+		}
+		"list_actionable_tasks" => {
+			let params: ListParams<TaskFilter> = parse_params(params)?;
+			into_result(TaskBmc::list_actionable(ctx, params.filter).await)
+		}
+		"update_tasks" => {
+			let params: BulkUpdateParams<TaskTarget, TaskForUpdate> = parse_params(params)?;
+			into_result(TaskBmc::update_many(ctx, params.target, params.data).await)
+		}
+		"delete_tasks" => {
+			let params: BulkDeleteParams<TaskTarget> = parse_params(params)?;
+			into_result(TaskBmc::delete_many(ctx, params.target).await)
+		}
+
+		_ => Err(RpcError::new(
+			METHOD_NOT_FOUND,
+			format!("Method not found: {method}"),
+		)),
+	}
+}
+
+fn parse_params<P: DeserializeOwned>(params: Value) -> Result<P, RpcError> {
+	serde_json::from_value(params).map_err(|ex| RpcError::new(INVALID_PARAMS, ex.to_string()))
+}
+
+fn into_result<D: Serialize>(res: crate::Result<D>) -> Result<Value, RpcError> {
+	match res {
+		Ok(data) => {
+			serde_json::to_value(data).map_err(|ex| RpcError::new(INTERNAL_ERROR, ex.to_string()))
+		}
+		Err(err) => Err(IpcError::from(err).into()),
+	}
+}
+
+// endregion: --- Dispatch
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::{
+		exec_rpc, parse_request, RpcOutput, RpcResponse, APP_ERROR, INVALID_REQUEST,
+		METHOD_NOT_FOUND,
+	};
+	use crate::model::test_utils::new_test_ctx;
+	use crate::model::ProjectBmc;
+	use serde_json::{json, Value};
+
+	#[test]
+	fn test_rpc_parse_request() -> anyhow::Result<()> {
+		// --- EXEC & CHECK - request
+		let request = parse_request(json!({"jsonrpc": "2.0", "id": 1, "method": "list_tasks"}))
+			.map_err(|_| anyhow::anyhow!("should be valid"))?;
+		assert_eq!(request.id, Some(json!(1)));
+		assert_eq!(request.method, "list_tasks");
+		assert_eq!(request.params, json!({}));
+
+		// --- EXEC & CHECK - notification vs null id
+		let request = parse_request(json!({"jsonrpc": "2.0", "method": "m", "params": [1]}))
+			.map_err(|_| anyhow::anyhow!("should be valid"))?;
+		assert_eq!(request.id, None);
+		let request = parse_request(json!({"jsonrpc": "2.0", "id": null, "method": "m"}))
+			.map_err(|_| anyhow::anyhow!("should be valid"))?;
+		assert_eq!(request.id, Some(Value::Null));
+
+		// --- EXEC & CHECK - invalid requests
+		for (request, id) in [
+			(json!(1), Value::Null),
+			(
+				json!({"jsonrpc": "1.0", "id": "a", "method": "m"}),
+				json!("a"),
+			),
+			(json!({"jsonrpc": "2.0", "id": 2, "method": 3}), json!(2)),
+			(
+				json!({"jsonrpc": "2.0", "id": {"a": 1}, "method": "m"}),
+				Value::Null,
+			),
+			(
+				json!({"jsonrpc": "2.0", "id": [1], "method": "m"}),
+				Value::Null,
+			),
+		] {
+			match parse_request(request) {
+				Ok(_) => panic!("should be invalid"),
+				Err(response) => {
+					assert_eq!(response.id, id);
+					assert_eq!(response.error.map(|e| e.code), Some(INVALID_REQUEST));
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_rpc_output_serialize() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let ok = RpcResponse::new(json!(1), Ok(json!({"id": "task:1"})));
+		let output = RpcOutput::Batch(vec![ok]);
+
+		// --- EXEC & CHECK
+		assert_eq!(
+			serde_json::to_value(output)?,
+			json!([{"jsonrpc": "2.0", "id": 1, "result": {"id": "task:1"}}])
+		);
+		assert_eq!(serde_json::to_value(RpcOutput::Empty)?, Value::Null);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_rpc_exec_batch_mixed() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, _events) = new_test_ctx().await?;
+		let batch = json!([
+			{"jsonrpc": "2.0", "id": 1, "method": "create_project", "params": {"data": {"name": "Project 01"}}},
+			{"jsonrpc": "2.0", "id": "b", "method": "create_project", "params": {"data": {"name": " "}}},
+			{"jsonrpc": "2.0", "method": "create_project", "params": {"data": {"name": "Project 02"}}},
+			{"jsonrpc": "2.0", "id": 3, "method": "list_projects"},
+		]);
+
+		// --- EXEC
+		let output = serde_json::to_value(exec_rpc(ctx.clone(), batch).await)?;
+
+		// --- CHECK
+		let responses = output.as_array().expect("batch output");
+		assert_eq!(responses.len(), 3, "no response for the notification");
+		assert_eq!(responses[0]["id"], 1);
+		assert!(responses[0]["result"]["id"].is_string());
+		assert_eq!(responses[1]["id"], "b");
+		assert_eq!(responses[1]["error"]["code"], APP_ERROR);
+		assert_eq!(responses[2]["id"], 3);
+		assert_eq!(
+			responses[2]["result"].as_array().map(|p| p.len()),
+			Some(2),
+			"notification executed"
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_rpc_exec_notification() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, _events) = new_test_ctx().await?;
+		let request = json!({"jsonrpc": "2.0", "method": "create_project", "params": {"data": {"name": "Project 01"}}});
+
+		// --- EXEC
+		let output = exec_rpc(ctx.clone(), request).await;
+
+		// --- CHECK
+		assert!(matches!(output, RpcOutput::Empty));
+		assert_eq!(ProjectBmc::list(ctx, None).await?.len(), 1);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_rpc_exec_method_not_found() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, _events) = new_test_ctx().await?;
+		let request = json!({"jsonrpc": "2.0", "id": 1, "method": "drop_everything"});
+
+		// --- EXEC
+		let output = serde_json::to_value(exec_rpc(ctx, request).await)?;
+
+		// --- CHECK
+		assert_eq!(output["id"], 1);
+		assert_eq!(output["error"]["code"], METHOD_NOT_FOUND);
+		assert!(output.get("result").is_none());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_rpc_exec_app_error() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, _events) = new_test_ctx().await?;
+		let request = json!({
+			"jsonrpc": "2.0",
+			"id": 1,
+			"method": "create_project",
+			"params": {"data": {"name": "Project 01", "color": "blue"}}
+		});
+
+		// --- EXEC
+		let output = serde_json::to_value(exec_rpc(ctx, request).await)?;
+
+		// --- CHECK
+		assert_eq!(output["error"]["code"], APP_ERROR);
+		let data = &output["error"]["data"];
+		assert_eq!(data["code"], "VALIDATION");
+		assert_eq!(data["field"], "data.color");
+		assert_eq!(data["details"]["violations"][0]["field"], "color");
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -0,0 +1,41 @@
+//! Tauri IPC commands for the live list subscriptions (see `model::subscription`).
+//!
+
+use crate::ctx::Ctx;
+use crate::ipc::{IdParams, IpcResponse, ParamsSummary, SubscribeListParams};
+use crate::model::{ListSubscriptionData, ModelMutateResultData, SubscriptionBmc};
+use crate::Error;
+use serde_json::Value;
+use tauri::{command, Window, Wry};
+use tracing::instrument;
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn subscribe_list(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: SubscribeListParams<Value>,
+) -> IpcResponse<ListSubscriptionData<Value>> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => SubscriptionBmc::subscribe_list(ctx, &params.entity, params.filter)
+			.await
+			.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn unsubscribe(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: IdParams,
+) -> IpcResponse<ModelMutateResultData> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => SubscriptionBmc::unsubscribe(ctx, &params.id)
+			.await
+			.map(|_| ModelMutateResultData::from(params.id))
+			.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
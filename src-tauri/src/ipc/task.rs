@@ -2,59 +2,61 @@
 //!
 
 use crate::ctx::Ctx;
-use crate::ipc::{CreateParams, DeleteParams, GetParams, IpcResponse, ListParams, UpdateParams};
-use crate::model::{ModelMutateResultData, Task, TaskBmc, TaskForCreate, TaskForUpdate};
+use crate::ipc::{BulkDeleteParams, BulkUpdateParams, IpcResponse, ListParams, ParamsSummary};
+use crate::model::{ModelMutateManyResultData, Task, TaskBmc, TaskForUpdate};
 use crate::Error;
 use serde_json::Value;
-use tauri::{command, AppHandle, Wry};
+use tauri::{command, Window, Wry};
+use tracing::instrument;
 
-#[command]
-pub async fn get_task(app: AppHandle<Wry>, params: GetParams) -> IpcResponse<Task> {
-	match Ctx::from_app(app) {
-		Ok(ctx) => TaskBmc::get(ctx, &params.id).await.into(),
-		Err(_) => Err(Error::CtxFail).into(),
-	}
-}
+// The CRUD commands (`get_task`, ..., `list_tasks`) are generated by the `TaskBmc` derive.
+pub use crate::model::task_commands::*;
 
+/// List the open tasks without open blockers (e.g., with a `project_id` filter for a project).
 #[command]
-pub async fn create_task(
-	app: AppHandle<Wry>,
-	params: CreateParams<TaskForCreate>,
-) -> IpcResponse<ModelMutateResultData> {
-	match Ctx::from_app(app) {
-		Ok(ctx) => TaskBmc::create(ctx, params.data).await.into(),
-		Err(_) => Err(Error::CtxFail).into(),
-	}
-}
-
-#[command]
-pub async fn update_task(
-	app: AppHandle<Wry>,
-	params: UpdateParams<TaskForUpdate>,
-) -> IpcResponse<ModelMutateResultData> {
-	match Ctx::from_app(app) {
-		Ok(ctx) => TaskBmc::update(ctx, &params.id, params.data).await.into(),
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn list_actionable_tasks(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: ListParams<Value>,
+) -> IpcResponse<Vec<Task>> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => match params.filter.map(serde_json::from_value).transpose() {
+			Ok(filter) => TaskBmc::list_actionable(ctx, filter).await.into(),
+			Err(err) => Err(Error::JsonSerde(err)).into(),
+		},
 		Err(_) => Err(Error::CtxFail).into(),
 	}
 }
 
+/// Update all of the tasks of the target (`{"filter": {..}}` or `{"ids": [..]}`) with the same patch.
 #[command]
-pub async fn delete_task(
-	app: AppHandle<Wry>,
-	params: DeleteParams,
-) -> IpcResponse<ModelMutateResultData> {
-	match Ctx::from_app(app) {
-		Ok(ctx) => TaskBmc::delete(ctx, &params.id).await.into(),
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn update_tasks(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: BulkUpdateParams<Value, TaskForUpdate>,
+) -> IpcResponse<ModelMutateManyResultData> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => match serde_json::from_value(params.target) {
+			Ok(target) => TaskBmc::update_many(ctx, target, params.data).await.into(),
+			Err(err) => Err(Error::JsonSerde(err)).into(),
+		},
 		Err(_) => Err(Error::CtxFail).into(),
 	}
 }
 
+/// Delete all of the tasks of the target (`{"filter": {..}}` or `{"ids": [..]}`).
 #[command]
-pub async fn list_tasks(app: AppHandle<Wry>, params: ListParams<Value>) -> IpcResponse<Vec<Task>> {
-	// TODO: Needs to make error handling simpler (use ? rather than all into())
-	match Ctx::from_app(app) {
-		Ok(ctx) => match params.filter.map(serde_json::from_value).transpose() {
-			Ok(filter) => TaskBmc::list(ctx, filter).await.into(),
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn delete_tasks(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: BulkDeleteParams<Value>,
+) -> IpcResponse<ModelMutateManyResultData> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => match serde_json::from_value(params.target) {
+			Ok(target) => TaskBmc::delete_many(ctx, target).await.into(),
 			Err(err) => Err(Error::JsonSerde(err)).into(),
 		},
 		Err(_) => Err(Error::CtxFail).into(),
@@ -0,0 +1,56 @@
+//! Tauri IPC commands to bridge TaskDep Frontend Model Controller to Backend Model Controller
+//!
+
+use crate::ctx::Ctx;
+use crate::ipc::{CreateParams, DeleteParams, IpcResponse, ListParams, ParamsSummary};
+use crate::model::{ModelMutateResultData, TaskDep, TaskDepBmc, TaskDepForCreate};
+use crate::Error;
+use serde_json::Value;
+use tauri::{command, Window, Wry};
+use tracing::instrument;
+
+/// Note: The data is deserialized here (rather than by Tauri) to return an IpcError on a non task id.
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn create_task_dep(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: CreateParams<Value>,
+) -> IpcResponse<ModelMutateResultData> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => match serde_json::from_value::<TaskDepForCreate>(params.data) {
+			Ok(val) => TaskDepBmc::create(ctx, val).await.into(),
+			Err(err) => Err(Error::JsonSerde(err)).into(),
+		},
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn delete_task_dep(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: DeleteParams,
+) -> IpcResponse<ModelMutateResultData> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => TaskDepBmc::delete(ctx, &params.id).await.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn list_task_deps(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: ListParams<Value>,
+) -> IpcResponse<Vec<TaskDep>> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => match params.filter.map(serde_json::from_value).transpose() {
+			Ok(filter) => TaskDepBmc::list(ctx, filter).await.into(),
+			Err(err) => Err(Error::JsonSerde(err)).into(),
+		},
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
@@ -0,0 +1,111 @@
+//! Tauri IPC commands to bridge TimeEntry Frontend Model Controller to Backend Model Controller
+//!
+
+use crate::ctx::Ctx;
+use crate::ipc::{DataParams, DeleteParams, IpcResponse, ListParams, ParamsSummary, UpdateParams};
+use crate::model::{
+	ModelMutateResultData, TimeEntry, TimeEntryBmc, TimeEntryForUpdate, TimeReport,
+	TimeReportQuery, TimerForStart,
+};
+use crate::Error;
+use serde_json::Value;
+use tauri::{command, Window, Wry};
+use tracing::instrument;
+
+/// Note: The data is deserialized here (rather than by Tauri) to return an IpcError on a non task id.
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn start_timer(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: DataParams<Value>,
+) -> IpcResponse<ModelMutateResultData> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => match serde_json::from_value::<TimerForStart>(params.data) {
+			Ok(val) => TimeEntryBmc::start_timer(ctx, val).await.into(),
+			Err(err) => Err(Error::JsonSerde(err)).into(),
+		},
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(request_id = request_id.as_deref(), outcome))]
+pub async fn stop_timer(
+	window: Window<Wry>,
+	request_id: Option<String>,
+) -> IpcResponse<ModelMutateResultData> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => TimeEntryBmc::stop_timer(ctx).await.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(request_id = request_id.as_deref(), outcome))]
+pub async fn get_running_timer(
+	window: Window<Wry>,
+	request_id: Option<String>,
+) -> IpcResponse<Option<TimeEntry>> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => TimeEntryBmc::running(ctx).await.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn update_time_entry(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: UpdateParams<TimeEntryForUpdate>,
+) -> IpcResponse<ModelMutateResultData> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => TimeEntryBmc::update(ctx, &params.id, params.data)
+			.await
+			.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn delete_time_entry(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: DeleteParams,
+) -> IpcResponse<ModelMutateResultData> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => TimeEntryBmc::delete(ctx, &params.id).await.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn list_time_entries(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: ListParams<Value>,
+) -> IpcResponse<Vec<TimeEntry>> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => match params.filter.map(serde_json::from_value).transpose() {
+			Ok(filter) => TimeEntryBmc::list(ctx, filter).await.into(),
+			Err(err) => Err(Error::JsonSerde(err)).into(),
+		},
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn get_time_report(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: DataParams<TimeReportQuery>,
+) -> IpcResponse<TimeReport> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => TimeEntryBmc::report(ctx, params.data).await.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
@@ -0,0 +1,42 @@
+//! Tauri IPC commands to open/focus the project windows (see `crate::window`).
+//!
+
+use crate::ctx::Ctx;
+use crate::ipc::{IdParams, IpcResponse, ParamsSummary};
+use crate::model::ProjectId;
+use crate::window::{self, ProjectWindowData};
+use crate::Error;
+use serde_json::Value;
+use tauri::{command, Window, Wry};
+use tracing::instrument;
+
+// Note: The ids are deserialized here (rather than by Tauri) to return an IpcError on a non project id.
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn open_project_window(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: IdParams<Value>,
+) -> IpcResponse<ProjectWindowData> {
+	let app = window.app_handle();
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => match serde_json::from_value::<ProjectId>(params.id) {
+			Ok(val) => window::open_project_window(ctx, app, val).await.into(),
+			Err(err) => Err(Error::JsonSerde(err)).into(),
+		},
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), outcome))]
+pub async fn focus_project_window(
+	window: Window<Wry>,
+	params: IdParams<Value>,
+) -> IpcResponse<ProjectWindowData> {
+	match serde_json::from_value::<ProjectId>(params.id) {
+		Ok(id) => window::focus_project_window(window.app_handle(), id).into(),
+		Err(err) => Err(Error::JsonSerde(err)).into(),
+	}
+}
@@ -0,0 +1,77 @@
+//! Tauri IPC commands to bridge Workspace Frontend Model Controller to Backend Model Controller
+//!
+
+use crate::ctx::Ctx;
+use crate::ipc::{CreateParams, DeleteParams, IdParams, IpcResponse, ParamsSummary, UpdateParams};
+use crate::model::{
+	ModelMutateResultData, Workspace, WorkspaceBmc, WorkspaceForCreate, WorkspaceForUpdate,
+};
+use crate::Error;
+use tauri::{command, Window, Wry};
+use tracing::instrument;
+
+#[command]
+#[instrument(skip_all, fields(request_id = request_id.as_deref(), outcome))]
+pub async fn list_workspaces(
+	window: Window<Wry>,
+	request_id: Option<String>,
+) -> IpcResponse<Vec<Workspace>> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => WorkspaceBmc::list(ctx).await.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn create_workspace(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: CreateParams<WorkspaceForCreate>,
+) -> IpcResponse<ModelMutateResultData> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => WorkspaceBmc::create(ctx, params.data).await.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn update_workspace(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: UpdateParams<WorkspaceForUpdate>,
+) -> IpcResponse<ModelMutateResultData> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => WorkspaceBmc::update(ctx, &params.id, params.data)
+			.await
+			.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn delete_workspace(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: DeleteParams,
+) -> IpcResponse<ModelMutateResultData> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => WorkspaceBmc::delete(ctx, &params.id).await.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
+
+#[command]
+#[instrument(skip_all, fields(params = %params.summary(), request_id = request_id.as_deref(), outcome))]
+pub async fn switch_workspace(
+	window: Window<Wry>,
+	request_id: Option<String>,
+	params: IdParams,
+) -> IpcResponse<Workspace> {
+	match Ctx::from_window(window, request_id) {
+		Ok(ctx) => WorkspaceBmc::switch(ctx, &params.id).await.into(),
+		Err(_) => Err(Error::CtxFail).into(),
+	}
+}
@@ -0,0 +1,70 @@
+//! Tracing setup of the app (see `main`), with the log files in the app log dir.
+//!
+//! The spans are nested by layer, each with its timing on close (i.e., `time.busy`, `time.idle`):
+//!   - The IPC commands (e.g., `update_task`, see `ipc::mod`), `info` level.
+//!   - The low level Backend Model Controller functions (e.g., `bmc_update`, see `model::bmc_base`), `debug` level.
+//!   - The store calls (e.g., `exec_merge`, see `model::store::surreal_store`), `debug` level, with their SurrealQL.
+//!
+//! Notes:
+//!   - The log files roll daily (e.g., `app.log.2023-06-20`), and are written by a background thread,
+//!     until the returned guard is dropped (i.e., at the end of `main`).
+//!   - In debug builds, the logs are also written to stdout.
+//!   - The level is of the config (see `config::LogConfig`), for the app code only
+//!     (the dependencies are at `warn`, e.g., surrealdb).
+//!
+
+use crate::config::LogLevel;
+use crate::prelude::*;
+use crate::Result;
+use std::fs;
+use std::path::Path;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+use tracing_subscriber::fmt;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// Prefix of the log files, in the app log dir.
+const LOG_FILE_PREFIX: &str = "app.log";
+
+/// Init the global tracing subscriber, keep the guard for the duration of the app (flushes the logs on drop).
+pub fn init_logging(app_log_dir: &Path, level: LogLevel) -> Result<WorkerGuard> {
+	fs::create_dir_all(app_log_dir)?;
+	let file_appender = tracing_appender::rolling::daily(app_log_dir, LOG_FILE_PREFIX);
+	let (file_writer, guard) = tracing_appender::non_blocking(file_appender);
+
+	let file_layer = fmt::layer()
+		.with_writer(file_writer)
+		.with_ansi(false)
+		.with_span_events(FmtSpan::CLOSE);
+
+	let stdout_layer =
+		cfg!(debug_assertions).then(|| fmt::layer().with_span_events(FmtSpan::CLOSE));
+
+	tracing_subscriber::registry()
+		.with(env_filter(level))
+		.with(file_layer)
+		.with(stdout_layer)
+		.init();
+
+	Ok(guard)
+}
+
+/// The level for the app, `warn` for the dependencies.
+fn env_filter(level: LogLevel) -> EnvFilter {
+	let level = LevelFilter::from(level);
+	EnvFilter::new(f!("warn,{}={level}", env!("CARGO_CRATE_NAME")))
+}
+
+impl From<LogLevel> for LevelFilter {
+	fn from(level: LogLevel) -> Self {
+		match level {
+			LogLevel::Error => LevelFilter::ERROR,
+			LogLevel::Warn => LevelFilter::WARN,
+			LogLevel::Info => LevelFilter::INFO,
+			LogLevel::Debug => LevelFilter::DEBUG,
+			LogLevel::Trace => LevelFilter::TRACE,
+		}
+	}
+}
@@ -9,43 +9,137 @@
 pub use error::{Error, Result};
 
 // -- Imports
-use model::{seed_store_for_dev, ModelStore};
+use config::{AppConfig, SeedMode};
+use event::{EventBus, TauriEmitter};
+use logging::init_logging;
+use model::{
+	is_store_empty, seed_store_for_dev, EventLogWriter, ModelStore, SubscriptionRefresher,
+};
 use std::sync::Arc;
+use tauri::Manager;
+use window::ProjectWindows;
 
 // -- Sub-Modules
+mod config;
 mod ctx;
 mod error;
 mod event;
 mod ipc;
+mod logging;
 mod model;
 mod prelude;
 mod utils;
+mod window;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-	let model_manager = ModelStore::new().await?;
+	let context = tauri::generate_context!();
+
+	let app_data_dir =
+		tauri::api::path::app_data_dir(context.config()).ok_or(Error::AppDataDirNotFound)?;
+
+	let app_config_dir =
+		tauri::api::path::app_config_dir(context.config()).ok_or(Error::AppConfigDirNotFound)?;
+	let config = AppConfig::load(&app_config_dir)?;
+
+	let app_log_dir =
+		tauri::api::path::app_log_dir(context.config()).ok_or(Error::AppLogDirNotFound)?;
+	// Note: Keep the guard until the end of main, to flush the logs.
+	let _log_guard = init_logging(&app_log_dir, config.log.level)?;
+	tracing::info!(?config, "app start");
+
+	// Note: In memory, the only engine of this build (see the surrealdb features of `Cargo.toml`).
+	let model_manager = ModelStore::new(&app_data_dir, "memory")
+		.await?
+		.with_event_window(config.event.window());
 	let model_manager = Arc::new(model_manager);
 
 	// for dev only
-	seed_store_for_dev(model_manager.clone()).await?;
+	let seed = &config.seed;
+	let do_seed = match seed.mode {
+		SeedMode::Always => true,
+		SeedMode::IfEmpty => is_store_empty(&model_manager).await?,
+		SeedMode::Never => false,
+	};
+	if do_seed {
+		seed_store_for_dev(model_manager.clone(), seed.projects, seed.tasks_per_project).await?;
+	}
 
 	tauri::Builder::default()
 		.manage(model_manager)
+		.setup(|app| {
+			let project_windows = Arc::new(ProjectWindows::default());
+			app.manage(project_windows.clone());
+
+			// The backend listeners of the HubEvents, with the frontend as just one of them
+			let event_bus = EventBus::default()
+				.with_listener(TauriEmitter::new(app.handle(), project_windows))
+				.with_async_listener(SubscriptionRefresher)
+				.with_async_listener(EventLogWriter);
+			app.manage(Arc::new(event_bus));
+			Ok(())
+		})
 		.invoke_handler(tauri::generate_handler![
+			// JSON-RPC 2.0 entry point
+			ipc::rpc,
 			// Project
 			ipc::get_project,
 			ipc::create_project,
 			ipc::update_project,
 			ipc::delete_project,
 			ipc::list_projects,
+			ipc::archive_project,
+			ipc::unarchive_project,
+			ipc::clone_project,
+			ipc::create_project_from_template,
 			// Task
 			ipc::get_task,
 			ipc::create_task,
 			ipc::update_task,
 			ipc::delete_task,
 			ipc::list_tasks,
+			ipc::list_actionable_tasks,
+			ipc::update_tasks,
+			ipc::delete_tasks,
+			// TaskDep
+			ipc::create_task_dep,
+			ipc::delete_task_dep,
+			ipc::list_task_deps,
+			// Comment
+			ipc::get_comment,
+			ipc::create_comment,
+			ipc::update_comment,
+			ipc::delete_comment,
+			ipc::list_comments,
+			// Attachment
+			ipc::get_attachment,
+			ipc::create_attachment,
+			ipc::delete_attachment,
+			ipc::list_attachments,
+			// TimeEntry
+			ipc::start_timer,
+			ipc::stop_timer,
+			ipc::get_running_timer,
+			ipc::update_time_entry,
+			ipc::delete_time_entry,
+			ipc::list_time_entries,
+			ipc::get_time_report,
+			// Workspace
+			ipc::list_workspaces,
+			ipc::create_workspace,
+			ipc::update_workspace,
+			ipc::delete_workspace,
+			ipc::switch_workspace,
+			// Subscription
+			ipc::subscribe_list,
+			ipc::unsubscribe,
+			// Event
+			ipc::events_since,
+			// Window
+			ipc::open_project_window,
+			ipc::focus_project_window,
 		])
-		.run(tauri::generate_context!())
+		.run(context)
 		.expect("error while running tauri application");
 
 	Ok(())
@@ -0,0 +1,334 @@
+//! All model and controller for the Attachment type
+//!
+//! An Attachment is the metadata of a local file attached to a task. The file content itself is copied
+//! into the content-addressed `BlobStore` (under the app data dir), and referenced by its `hash`.
+//!
+
+use super::bmc_base::{bmc_create, bmc_delete, bmc_get, bmc_list};
+use super::store::{guess_mime, Creatable, Filterable, Nanos};
+use super::validate::{Validate, Violations, TITLE_RULE};
+use super::{ModelMutateResultData, TaskBmc, TaskId};
+use crate::ctx::Ctx;
+use crate::{Error, Result};
+use modql::filter::{FilterNodes, OpValString, OpValsString};
+use modql::ListOptions;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use ts_rs::TS;
+
+// region:    --- Attachment
+
+#[derive(Serialize, Deserialize, TS, Debug, Clone)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct Attachment {
+	pub id: String,
+	pub ctime: Nanos,
+	pub task_id: TaskId,
+
+	pub name: String,
+	/// In bytes (serialized as a JSON number)
+	#[ts(type = "number")]
+	pub size: u64,
+	pub mime: String,
+	pub hash: String,
+
+	/// Local path of the stored file (set by AttachmentBmc, empty in the Model events)
+	#[serde(default)]
+	pub path: String,
+}
+
+// endregion: --- Attachment
+
+// region:    --- AttachmentForCreate
+
+/// What the caller gives, the metadata are computed when copying the file.
+#[derive(Deserialize, TS, Debug)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct AttachmentForCreate {
+	pub task_id: TaskId,
+	/// Local path of the file to attach
+	pub file_path: String,
+	/// Name to display (default to the file name)
+	pub name: Option<String>,
+}
+
+impl Validate for AttachmentForCreate {
+	fn check(&self, violations: &mut Violations) {
+		violations.opt_text("name", self.name.as_deref(), TITLE_RULE);
+	}
+}
+
+/// The attachment record as stored.
+#[derive(Serialize)]
+struct AttachmentData {
+	task_id: TaskId,
+	name: String,
+	size: u64,
+	mime: String,
+	hash: String,
+}
+
+impl Creatable for AttachmentData {}
+
+/// Note: Validated as `AttachmentForCreate`, before the file gets copied.
+impl Validate for AttachmentData {}
+
+// endregion: --- AttachmentForCreate
+
+// region:    --- AttachmentFilter
+
+#[derive(FilterNodes, Deserialize, Debug)]
+pub struct AttachmentFilter {
+	pub task_id: Option<OpValsString>,
+	pub name: Option<OpValsString>,
+	pub mime: Option<OpValsString>,
+}
+
+impl Filterable for AttachmentFilter {}
+
+// endregion: --- AttachmentFilter
+
+// region:    --- AttachmentBmc
+
+pub struct AttachmentBmc;
+
+impl AttachmentBmc {
+	const ENTITY: &'static str = "attachment";
+
+	pub async fn get(ctx: Arc<Ctx>, id: &str) -> Result<Attachment> {
+		let attachment = bmc_get::<Attachment>(ctx.clone(), Self::ENTITY, id).await?;
+		Ok(with_path(&ctx, attachment))
+	}
+
+	/// Copy the file into the blob store (deduplicated by content) and create its attachment record.
+	///
+	/// Note: The task (not read-only) and the name are checked before the file gets copied, and the blob is collected
+	///       if the record cannot be created.
+	pub async fn create(ctx: Arc<Ctx>, data: AttachmentForCreate) -> Result<ModelMutateResultData> {
+		let file_path = PathBuf::from(&data.file_path);
+		let name = match data.name {
+			Some(name) => name,
+			None => file_path
+				.file_name()
+				.map(|n| n.to_string_lossy().to_string())
+				.unwrap_or_else(|| data.file_path.clone()),
+		};
+		let data = AttachmentForCreate {
+			name: Some(name),
+			..data
+		};
+		data.validate()?;
+		TaskBmc::get_writable(ctx.clone(), &data.task_id).await?;
+
+		let model_manager = ctx.get_model_manager();
+		let blob_guard = model_manager.lock_blobs().await;
+		let blob_store = model_manager.blob_store(&ctx);
+		let (blob_info, mime) = tokio::task::spawn_blocking(move || {
+			let blob_info = blob_store.put_file(&file_path)?;
+			Ok::<_, Error>((blob_info, guess_mime(&file_path)))
+		})
+		.await
+		.map_err(|ex| Error::AttachmentFailToStore(ex.to_string()))??;
+
+		let data = AttachmentData {
+			task_id: data.task_id,
+			name: data.name.unwrap_or_default(),
+			size: blob_info.size,
+			mime,
+			hash: blob_info.hash,
+		};
+
+		let res = bmc_create::<Attachment, _>(ctx.clone(), Self::ENTITY, data).await;
+		drop(blob_guard);
+		if res.is_err() {
+			if let Err(ex) = Self::gc_blobs(ctx).await {
+				tracing::warn!(error = %ex, "attachment blob gc failed");
+			}
+		}
+
+		res
+	}
+
+	/// Delete the attachment record, and its blob if not used by another attachment.
+	pub async fn delete(ctx: Arc<Ctx>, id: &str) -> Result<ModelMutateResultData> {
+		let attachment = Self::get(ctx.clone(), id).await?;
+		TaskBmc::get_writable(ctx.clone(), &attachment.task_id).await?;
+
+		let result_data = bmc_delete::<Attachment>(ctx.clone(), Self::ENTITY, id).await?;
+		Self::gc_blobs(ctx).await?;
+		Ok(result_data)
+	}
+
+	pub async fn list(ctx: Arc<Ctx>, filter: Option<AttachmentFilter>) -> Result<Vec<Attachment>> {
+		let opts = ListOptions {
+			limit: None,
+			offset: None,
+			order_bys: Some("ctime".into()),
+		};
+		let attachments: Vec<Attachment> =
+			bmc_list(ctx.clone(), Self::ENTITY, filter, opts).await?;
+		Ok(attachments
+			.into_iter()
+			.map(|a| with_path(&ctx, a))
+			.collect())
+	}
+
+	/// Delete all of the attachments of a task (e.g., when the task gets deleted) and their unused blobs.
+	pub async fn delete_for_task(ctx: Arc<Ctx>, task_id: &str) -> Result<()> {
+		let filter = AttachmentFilter {
+			task_id: Some(OpValString::Eq(task_id.to_string()).into()),
+			name: None,
+			mime: None,
+		};
+		for attachment in Self::list(ctx.clone(), Some(filter)).await? {
+			bmc_delete::<Attachment>(ctx.clone(), Self::ENTITY, &attachment.id).await?;
+		}
+
+		Self::gc_blobs(ctx).await
+	}
+
+	/// Delete the attachments of the tasks (and their unreferenced blobs), in one store statement,
+	/// without their `Model` events (i.e., for `TaskBmc::delete_many`, its event covering them).
+	pub(super) async fn delete_for_tasks(ctx: Arc<Ctx>, task_ids: &[String]) -> Result<()> {
+		let filter = AttachmentFilter {
+			task_id: Some(OpValString::In(task_ids.to_vec()).into()),
+			name: None,
+			mime: None,
+		};
+		let ids: Vec<String> = Self::list(ctx.clone(), Some(filter))
+			.await?
+			.into_iter()
+			.map(|a| a.id)
+			.collect();
+		ctx.get_model_manager()
+			.store(&ctx)
+			.exec_delete_many(Self::ENTITY, &ids)
+			.await?;
+
+		Self::gc_blobs(ctx).await
+	}
+
+	/// Delete the blobs which are not referenced by any attachment record.
+	///
+	/// Note: Holds the blob lock, so the blob of an attachment being created (i.e., without its record yet) is kept.
+	async fn gc_blobs(ctx: Arc<Ctx>) -> Result<()> {
+		let model_manager = ctx.get_model_manager();
+		let _blob_guard = model_manager.lock_blobs().await;
+		let referenced: HashSet<String> = bmc_list::<Attachment, AttachmentFilter>(
+			ctx.clone(),
+			Self::ENTITY,
+			None,
+			ListOptions::default(),
+		)
+		.await?
+		.into_iter()
+		.map(|a| a.hash)
+		.collect();
+
+		let blob_store = model_manager.blob_store(&ctx);
+		tokio::task::spawn_blocking(move || blob_store.retain(&referenced))
+			.await
+			.map_err(|ex| Error::AttachmentFailToStore(ex.to_string()))??;
+
+		Ok(())
+	}
+}
+
+fn with_path(ctx: &Ctx, mut attachment: Attachment) -> Attachment {
+	attachment.path = ctx
+		.get_model_manager()
+		.blob_store(ctx)
+		.path_for(&attachment.hash)
+		.to_string_lossy()
+		.to_string();
+	attachment
+}
+
+// endregion: --- AttachmentBmc
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_utils::{create_test_project, new_test_ctx, task_for_create};
+	use crate::model::TaskBmc;
+	use std::path::Path;
+
+	#[tokio::test]
+	async fn test_attachment_create_while_gc() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, _events) = new_test_ctx().await?;
+		let project_id = create_test_project(ctx.clone(), "Project 01").await?;
+		let task_id = TaskBmc::create(ctx.clone(), task_for_create(&project_id, "Task 01"))
+			.await?
+			.id;
+		let tmp_dir = tempfile::tempdir()?;
+		let attachment_data = |content: &str| -> anyhow::Result<AttachmentForCreate> {
+			let file_path = tmp_dir.path().join(format!("{content}.txt"));
+			std::fs::write(&file_path, content)?;
+			Ok(AttachmentForCreate {
+				task_id: TaskId::from_store(task_id.clone()),
+				file_path: file_path.to_string_lossy().to_string(),
+				name: None,
+			})
+		};
+		let id_a = AttachmentBmc::create(ctx.clone(), attachment_data("A")?)
+			.await?
+			.id;
+
+		// --- EXEC - delete (and gc) while creating
+		let data_b = attachment_data("B")?;
+		let (deleted, created) = tokio::join!(
+			AttachmentBmc::delete(ctx.clone(), &id_a),
+			AttachmentBmc::create(ctx.clone(), data_b)
+		);
+		deleted?;
+
+		// --- CHECK
+		let attachment_b = AttachmentBmc::get(ctx.clone(), &created?.id).await?;
+		assert!(Path::new(&attachment_b.path).exists(), "blob of B kept");
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_attachment_create_invalid_no_blob() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, _events) = new_test_ctx().await?;
+		let project_id = create_test_project(ctx.clone(), "Project 01").await?;
+		let task_id = TaskBmc::create(ctx.clone(), task_for_create(&project_id, "Task 01"))
+			.await?
+			.id;
+		let tmp_dir = tempfile::tempdir()?;
+		let file_path = tmp_dir.path().join("A.txt");
+		std::fs::write(&file_path, "A")?;
+		let attachment_data = |task_id: &str, name: Option<String>| AttachmentForCreate {
+			task_id: TaskId::from_store(task_id.to_string()),
+			file_path: file_path.to_string_lossy().to_string(),
+			name,
+		};
+		let blob_dir = ctx.get_model_manager().blob_dir(ctx.workspace_db());
+
+		// --- EXEC
+		let task_res = AttachmentBmc::create(ctx.clone(), attachment_data("task:nope", None)).await;
+		let name_res = AttachmentBmc::create(
+			ctx.clone(),
+			attachment_data(&task_id, Some("a".repeat(300))),
+		)
+		.await;
+
+		// --- CHECK
+		assert!(matches!(task_res, Err(Error::StoreRecordNotFound(_))));
+		assert!(matches!(name_res, Err(Error::Validation(_))));
+		let blob_count = std::fs::read_dir(&blob_dir)
+			.map(|entries| entries.count())
+			.unwrap_or(0);
+		assert_eq!(blob_count, 0, "no blob stored");
+		assert!(AttachmentBmc::list(ctx.clone(), None).await?.is_empty());
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -1,75 +1,122 @@
 //! Base and low level Backend Model Controller functions
 //!
 
-use super::store::{Creatable, Filterable, Patchable};
-use super::{fire_model_event, ModelMutateResultData};
+use super::store::{from_object, Creatable, Filterable, Patchable};
+use super::validate::Validate;
+use super::{fire_model_event, ModelEventData, ModelMutateResultData};
 use crate::ctx::Ctx;
-use crate::{Error, Result};
+use crate::Result;
 use modql::ListOptions;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::BTreeSet;
 use std::sync::Arc;
 use surrealdb::sql::Object;
+use tracing::instrument;
 
-pub(super) async fn bmc_get<E>(ctx: Arc<Ctx>, _entity: &'static str, id: &str) -> Result<E>
+#[instrument(level = "debug", skip_all, fields(entity = entity, id = id))]
+pub(super) async fn bmc_get<E>(ctx: Arc<Ctx>, entity: &'static str, id: &str) -> Result<E>
 where
-	E: TryFrom<Object, Error = Error>,
+	E: DeserializeOwned,
 {
-	ctx.get_model_manager()
-		.store()
-		.exec_get(id)
-		.await?
-		.try_into()
+	let obj = ctx
+		.get_model_manager()
+		.store(&ctx)
+		.exec_get(entity, id)
+		.await?;
+	from_object(obj)
 }
 
-pub(super) async fn bmc_create<D>(
+/// Create the entity, and fire the `create` event with the created entity.
+#[instrument(level = "debug", skip_all, fields(entity = entity))]
+pub(super) async fn bmc_create<E, D>(
 	ctx: Arc<Ctx>,
 	entity: &'static str,
 	data: D,
 ) -> Result<ModelMutateResultData>
 where
-	D: Creatable,
+	E: DeserializeOwned + Serialize + Clone,
+	D: Creatable + Validate,
 {
-	let id = ctx
-		.get_model_manager()
-		.store()
-		.exec_create(entity, data)
-		.await?;
-	let result_data = ModelMutateResultData::from(id);
+	data.validate()?;
+
+	let model_manager = ctx.get_model_manager();
+	let store = model_manager.store(&ctx);
+	let id = store.exec_create(entity, data).await?;
+	let created: E = from_object(store.exec_get(entity, &id).await?)?;
 
-	fire_model_event(&ctx, entity, "create", result_data.clone());
+	fire_model_event(
+		&ctx,
+		entity,
+		"create",
+		ModelEventData::created(id.clone(), created),
+	);
 
-	Ok(result_data)
+	Ok(ModelMutateResultData::from(id))
 }
 
-pub(super) async fn bmc_update<D>(
+/// Update the entity, and fire the `update` event with the updated entity and the changed properties.
+///
+/// Note: Fails with `Error::StoreRecordNotFound` if the entity does not exist (rather than creating it).
+#[instrument(level = "debug", skip_all, fields(entity = entity, id = id))]
+pub(super) async fn bmc_update<E, D>(
 	ctx: Arc<Ctx>,
 	entity: &'static str,
 	id: &str,
 	data: D,
 ) -> Result<ModelMutateResultData>
 where
-	D: Patchable,
+	E: DeserializeOwned + Serialize + Clone,
+	D: Patchable + Validate,
 {
-	let id = ctx.get_model_manager().store().exec_merge(id, data).await?;
+	data.validate()?;
 
-	let result_data = ModelMutateResultData::from(id);
-	fire_model_event(&ctx, entity, "update", result_data.clone());
+	let model_manager = ctx.get_model_manager();
+	let store = model_manager.store(&ctx);
+	let before = store.exec_get(entity, id).await?;
+	let id = store.exec_merge(entity, id, data).await?;
+	let after = store.exec_get(entity, &id).await?;
 
-	Ok(result_data)
+	let changed = changed_props(&before, &after);
+	let updated: E = from_object(after)?;
+
+	fire_model_event(
+		&ctx,
+		entity,
+		"update",
+		ModelEventData::updated(id.clone(), updated, changed),
+	);
+
+	Ok(ModelMutateResultData::from(id))
 }
 
-pub(super) async fn bmc_delete(
+/// Delete the entity, and fire the `delete` event with the deleted entity.
+#[instrument(level = "debug", skip_all, fields(entity = entity, id = id))]
+pub(super) async fn bmc_delete<E>(
 	ctx: Arc<Ctx>,
 	entity: &'static str,
 	id: &str,
-) -> Result<ModelMutateResultData> {
-	let id = ctx.get_model_manager().store().exec_delete(id).await?;
-	let result_data = ModelMutateResultData::from(id);
+) -> Result<ModelMutateResultData>
+where
+	E: DeserializeOwned + Serialize + Clone,
+{
+	let model_manager = ctx.get_model_manager();
+	let store = model_manager.store(&ctx);
+	let deleted: E = from_object(store.exec_get(entity, id).await?)?;
+	let id = store.exec_delete(entity, id).await?;
 
-	fire_model_event(&ctx, entity, "delete", result_data.clone());
+	fire_model_event(
+		&ctx,
+		entity,
+		"delete",
+		ModelEventData::deleted(id.clone(), deleted),
+	);
 
-	Ok(result_data)
+	Ok(ModelMutateResultData::from(id))
 }
 
+/// Note: Only the presence of the filter is traced, not its values (i.e., user data).
+#[instrument(level = "debug", skip_all, fields(entity = entity, filter = filter.is_some()))]
 pub(super) async fn bmc_list<E, F>(
 	ctx: Arc<Ctx>,
 	entity: &'static str,
@@ -77,19 +124,60 @@ pub(super) async fn bmc_list<E, F>(
 	opts: ListOptions,
 ) -> Result<Vec<E>>
 where
-	E: TryFrom<Object, Error = Error>,
+	E: DeserializeOwned,
 	F: Filterable + std::fmt::Debug,
 {
 	// query for the Surreal Objects
 	let objects = ctx
 		.get_model_manager()
-		.store()
+		.store(&ctx)
 		.exec_select(entity, filter.map(|f| f.filter_nodes(None)), opts)
 		.await?;
 
 	// then get the entities
-	objects
+	objects.into_iter().map(from_object).collect::<Result<_>>()
+}
+
+/// The properties with a different value after the mutation (sorted).
+pub(super) fn changed_props(before: &Object, after: &Object) -> Vec<String> {
+	let props: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+	props
 		.into_iter()
-		.map(|o| o.try_into())
-		.collect::<Result<_>>()
+		.filter(|prop| before.get(*prop) != after.get(*prop))
+		.cloned()
+		.collect()
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::changed_props;
+	use crate::utils::map;
+	use surrealdb::sql::Object;
+
+	#[test]
+	fn test_changed_props() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let before: Object = map![
+			"title".into() => "Task A".into(),
+			"done".into() => false.into(),
+			"desc".into() => "Some desc".into(),
+		]
+		.into();
+		let after: Object = map![
+			"title".into() => "Task A".into(),
+			"done".into() => true.into(),
+			"due_date".into() => "2023-07-01".into(),
+		]
+		.into();
+
+		// --- EXEC
+		let changed = changed_props(&before, &after);
+
+		// --- CHECK
+		assert_eq!(changed, vec!["desc", "done", "due_date"]);
+
+		Ok(())
+	}
 }
+// endregion: --- Tests
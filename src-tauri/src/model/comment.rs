@@ -0,0 +1,273 @@
+//! All model and controller for the Comment type
+//!
+
+use super::bmc_base::{bmc_create, bmc_delete, bmc_get, bmc_list, bmc_update};
+use super::store::{Creatable, Filterable, Nanos, Patchable};
+use super::validate::{Validate, Violations, BODY_RULE};
+use super::{ModelMutateResultData, TaskBmc, TaskId};
+use crate::ctx::Ctx;
+use crate::Result;
+use modql::filter::{FilterNodes, OpValString, OpValsString};
+use modql::ListOptions;
+use serde::{Deserialize, Serialize};
+use serde_with_macros::skip_serializing_none;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use surrealdb::sql::Datetime;
+use ts_rs::TS;
+
+// region:    --- Comment
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, TS, Debug, Clone)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct Comment {
+	pub id: String,
+	pub ctime: Nanos,
+	/// Last time the body was edited (None if never edited)
+	pub mtime: Option<Nanos>,
+	pub task_id: TaskId,
+
+	pub body: String,
+}
+
+// endregion: --- Comment
+
+// region:    --- CommentForCreate
+
+#[derive(Serialize, Deserialize, TS, Debug)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct CommentForCreate {
+	pub task_id: TaskId,
+	pub body: String,
+}
+
+impl Creatable for CommentForCreate {}
+
+impl Validate for CommentForCreate {
+	fn check(&self, violations: &mut Violations) {
+		violations.text("body", &self.body, BODY_RULE);
+	}
+}
+
+// endregion: --- CommentForCreate
+
+// region:    --- CommentForUpdate
+
+#[skip_serializing_none]
+#[derive(Deserialize, TS, Debug)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct CommentForUpdate {
+	pub body: Option<String>,
+}
+
+impl Validate for CommentForUpdate {
+	fn check(&self, violations: &mut Violations) {
+		violations.opt_text("body", self.body.as_deref(), BODY_RULE);
+	}
+}
+
+/// The comment changes as stored, with the `mtime` of the body edit.
+#[derive(Serialize)]
+struct CommentPatch {
+	body: Option<String>,
+	mtime: Option<i64>,
+}
+
+impl From<CommentForUpdate> for CommentPatch {
+	fn from(val: CommentForUpdate) -> Self {
+		let mtime = val
+			.body
+			.as_ref()
+			.map(|_| Datetime::default().timestamp_nanos());
+		CommentPatch {
+			body: val.body,
+			mtime,
+		}
+	}
+}
+
+impl Patchable for CommentPatch {}
+
+/// Note: Validated as `CommentForUpdate`.
+impl Validate for CommentPatch {}
+
+// endregion: --- CommentForUpdate
+
+// region:    --- CommentFilter
+
+#[derive(FilterNodes, Deserialize, Debug)]
+pub struct CommentFilter {
+	pub task_id: Option<OpValsString>,
+	pub body: Option<OpValsString>,
+}
+
+impl Filterable for CommentFilter {}
+
+// endregion: --- CommentFilter
+
+// region:    --- CommentBmc
+
+pub struct CommentBmc;
+
+impl CommentBmc {
+	const ENTITY: &'static str = "comment";
+
+	pub async fn get(ctx: Arc<Ctx>, id: &str) -> Result<Comment> {
+		bmc_get::<Comment>(ctx, Self::ENTITY, id).await
+	}
+
+	pub async fn create(ctx: Arc<Ctx>, data: CommentForCreate) -> Result<ModelMutateResultData> {
+		// make sure the task exists (its comments are deleted with it), and is not read-only
+		TaskBmc::get_writable(ctx.clone(), &data.task_id).await?;
+
+		bmc_create::<Comment, _>(ctx, Self::ENTITY, data).await
+	}
+
+	pub async fn update(
+		ctx: Arc<Ctx>,
+		id: &str,
+		data: CommentForUpdate,
+	) -> Result<ModelMutateResultData> {
+		data.validate()?;
+		let comment = Self::get(ctx.clone(), id).await?;
+		TaskBmc::get_writable(ctx.clone(), &comment.task_id).await?;
+
+		bmc_update::<Comment, _>(ctx, Self::ENTITY, id, CommentPatch::from(data)).await
+	}
+
+	pub async fn delete(ctx: Arc<Ctx>, id: &str) -> Result<ModelMutateResultData> {
+		let comment = Self::get(ctx.clone(), id).await?;
+		TaskBmc::get_writable(ctx.clone(), &comment.task_id).await?;
+
+		bmc_delete::<Comment>(ctx, Self::ENTITY, id).await
+	}
+
+	pub async fn list(ctx: Arc<Ctx>, filter: Option<CommentFilter>) -> Result<Vec<Comment>> {
+		let opts = ListOptions {
+			limit: None,
+			offset: None,
+			order_bys: Some("ctime".into()),
+		};
+		bmc_list(ctx, Self::ENTITY, filter, opts).await
+	}
+
+	/// Number of comments per task_id (tasks without comments are not in the map).
+	pub async fn count_by_task(ctx: Arc<Ctx>) -> Result<BTreeMap<String, u32>> {
+		ctx.get_model_manager()
+			.store(&ctx)
+			.exec_count_group_by(Self::ENTITY, "task_id")
+			.await
+	}
+
+	/// Delete all of the comments of a task (e.g., when the task gets deleted).
+	pub async fn delete_for_task(ctx: Arc<Ctx>, task_id: &str) -> Result<()> {
+		let filter = CommentFilter {
+			task_id: Some(OpValString::Eq(task_id.to_string()).into()),
+			body: None,
+		};
+		for comment in Self::list(ctx.clone(), Some(filter)).await? {
+			bmc_delete::<Comment>(ctx.clone(), Self::ENTITY, &comment.id).await?;
+		}
+
+		Ok(())
+	}
+
+	/// Delete the comments of the tasks, in one store statement, without their `Model` events
+	/// (i.e., for `TaskBmc::delete_many`, its event covering them).
+	pub(super) async fn delete_for_tasks(ctx: Arc<Ctx>, task_ids: &[String]) -> Result<()> {
+		let filter = CommentFilter {
+			task_id: Some(OpValString::In(task_ids.to_vec()).into()),
+			body: None,
+		};
+		let ids: Vec<String> = Self::list(ctx.clone(), Some(filter))
+			.await?
+			.into_iter()
+			.map(|e| e.id)
+			.collect();
+		ctx.get_model_manager()
+			.store(&ctx)
+			.exec_delete_many(Self::ENTITY, &ids)
+			.await?;
+
+		Ok(())
+	}
+}
+
+// endregion: --- CommentBmc
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_utils::{create_test_project, new_test_ctx, task_for_create};
+	use crate::Error;
+
+	fn comment_data(task_id: &str, body: &str) -> CommentForCreate {
+		CommentForCreate {
+			task_id: TaskId::from_store(task_id.to_string()),
+			body: body.to_string(),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_comment_bmc_create_task_not_found() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, events) = new_test_ctx().await?;
+		let project_id = create_test_project(ctx.clone(), "Project 01").await?;
+		TaskBmc::create(ctx.clone(), task_for_create(&project_id, "Task 01")).await?;
+		events.take("Model");
+
+		// --- EXEC
+		let res = CommentBmc::create(ctx.clone(), comment_data("task:nope", "Comment 01")).await;
+
+		// --- CHECK
+		assert!(matches!(res, Err(Error::StoreRecordNotFound(_))));
+		assert!(CommentBmc::list(ctx.clone(), None).await?.is_empty());
+		assert!(events.take("Model").is_empty(), "no event when failing");
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_comment_bmc_deleted_with_task() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, events) = new_test_ctx().await?;
+		let project_id = create_test_project(ctx.clone(), "Project 01").await?;
+		let task_01 = TaskBmc::create(ctx.clone(), task_for_create(&project_id, "Task 01"))
+			.await?
+			.id;
+		let task_02 = TaskBmc::create(ctx.clone(), task_for_create(&project_id, "Task 02"))
+			.await?
+			.id;
+		let comment_01 = CommentBmc::create(ctx.clone(), comment_data(&task_01, "Comment 01"))
+			.await?
+			.id;
+		CommentBmc::create(ctx.clone(), comment_data(&task_01, "Comment 02")).await?;
+		let comment_03 = CommentBmc::create(ctx.clone(), comment_data(&task_02, "Comment 03"))
+			.await?
+			.id;
+		events.take("Model");
+
+		// --- EXEC
+		TaskBmc::delete(ctx.clone(), &task_01).await?;
+
+		// --- CHECK
+		let comments = CommentBmc::list(ctx.clone(), None).await?;
+		assert_eq!(comments.len(), 1);
+		assert_eq!(comments[0].id, comment_03);
+		assert!(matches!(
+			CommentBmc::get(ctx.clone(), &comment_01).await,
+			Err(Error::StoreRecordNotFound(_))
+		));
+		let evts = events.take("Model");
+		let comment_deletes = evts
+			.iter()
+			.filter(|evt| evt.topic == "comment" && evt.label.as_deref() == Some("delete"))
+			.count();
+		assert_eq!(comment_deletes, 2, "one delete event per comment");
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
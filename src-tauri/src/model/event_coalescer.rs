@@ -0,0 +1,365 @@
+//! Coalescing of the `Model` events of the bursts of mutations (e.g., seeding, bulk edits),
+//! so the listeners (e.g., the frontend views) get one event rather than hundreds.
+//!
+//! The events fired within the window (from the first one) are kept, and then emitted in order,
+//! with the consecutive events of the same entity as one `Model` event (label: `batch`, data: `ModelEventBatch`).
+//!
+//! Notes:
+//!   - A single event (i.e., not part of a burst) is emitted as is (label: its action), after the window.
+//!   - With a zero window (e.g., in tests), the events are emitted right away, as is.
+//!   - The events are numbered (see `event_log`) when emitted, so a batch has one seq.
+//!   - The client request id (see `Ctx::request_id`) is kept per event (i.e., on the `ModelBatchEvent`s), and on the
+//!     emitted event when all of its events have the same one.
+//!   - The ctx of each event is kept, and only the events of the same window and workspace are batched,
+//!     so the listeners get the ctx of the call (e.g., `Ctx::window_label`, `Ctx::workspace_db`).
+//!   - The events of a single project (e.g., its tasks) are only emitted to the windows showing it
+//!     (see `EventTarget::Project`).
+//!
+
+use super::ProjectId;
+use crate::ctx::Ctx;
+use crate::event::{EventTarget, HubEvent};
+use parking_lot::Mutex;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use std::sync::Arc;
+use std::time::Duration;
+use ts_rs::TS;
+
+/// Default window of the `EventCoalescer` (see `ModelStore::with_event_window`).
+pub const DEFAULT_EVENT_WINDOW: Duration = Duration::from_millis(50);
+
+// region:    --- ModelEventBatch
+
+/// Data of the `Model` `batch` events, the events of the entity (topic) in order.
+#[derive(TS, Serialize, Clone, Debug)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct ModelEventBatch {
+	pub events: Vec<ModelBatchEvent>,
+}
+
+#[derive(TS, Serialize, Clone, Debug)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct ModelBatchEvent {
+	/// The label of the event (e.g., `create`, `update_many`)
+	pub action: String,
+	/// The data of the event (e.g., `ModelEventData`, `ModelMutateManyResultData`)
+	#[ts(type = "any")]
+	pub data: Option<JsonValue>,
+	/// The client request id of the call which fired the event (see `Ctx::request_id`)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub request_id: Option<String>,
+}
+
+// endregion: --- ModelEventBatch
+
+// region:    --- EventCoalescer
+
+struct PendingEvent {
+	ctx: Arc<Ctx>,
+	entity: String,
+	action: String,
+	data: Option<JsonValue>,
+}
+
+/// Held by the `ModelStore`.
+pub struct EventCoalescer {
+	window: Duration,
+	pending: Arc<Mutex<Vec<PendingEvent>>>,
+}
+
+impl EventCoalescer {
+	pub(in crate::model) fn new(window: Duration) -> Self {
+		EventCoalescer {
+			window,
+			pending: Arc::default(),
+		}
+	}
+
+	/// Keep the event, the first one of the window schedules the flush.
+	pub(in crate::model) fn push(
+		&self,
+		ctx: &Arc<Ctx>,
+		entity: &str,
+		action: &str,
+		data: Option<JsonValue>,
+	) {
+		let event = PendingEvent {
+			ctx: ctx.clone(),
+			entity: entity.to_string(),
+			action: action.to_string(),
+			data,
+		};
+
+		if self.window.is_zero() {
+			emit(vec![event]);
+			return;
+		}
+
+		let mut pending = self.pending.lock();
+		let first = pending.is_empty();
+		pending.push(event);
+
+		if first {
+			let pending = self.pending.clone();
+			let window = self.window;
+			tokio::spawn(async move {
+				tokio::time::sleep(window).await;
+				let events = std::mem::take(&mut *pending.lock());
+				for group in group_by_entity(events) {
+					emit(group);
+				}
+			});
+		}
+	}
+}
+
+// endregion: --- EventCoalescer
+
+// region:    --- Coalescer Utils
+
+/// Group the consecutive events of the same entity and origin (see `same_origin`), keeping the order.
+fn group_by_entity(events: Vec<PendingEvent>) -> Vec<Vec<PendingEvent>> {
+	let mut groups: Vec<Vec<PendingEvent>> = Vec::new();
+	for event in events {
+		match groups.last_mut() {
+			Some(group) if group[0].entity == event.entity && same_origin(&group[0], &event) => {
+				group.push(event)
+			}
+			_ => groups.push(vec![event]),
+		}
+	}
+	groups
+}
+
+/// If the events are of calls of the same window and workspace (the request ids can differ).
+fn same_origin(a: &PendingEvent, b: &PendingEvent) -> bool {
+	a.ctx.window_label() == b.ctx.window_label() && a.ctx.workspace_db() == b.ctx.workspace_db()
+}
+
+/// Emit the events of the entity, as one event (number and log it, see `event_log`), with the ctx of the first one.
+fn emit(mut group: Vec<PendingEvent>) {
+	let ctx = group[0].ctx.clone();
+	let target = group_target(&group);
+	let request_id = group_request_id(&group);
+	let (entity, label, data) = if group.len() == 1 {
+		let event = group.remove(0);
+		(event.entity, event.action, event.data)
+	} else {
+		let entity = group[0].entity.clone();
+		let batch = ModelEventBatch {
+			events: group
+				.into_iter()
+				.map(|event| ModelBatchEvent {
+					request_id: event.ctx.request_id().map(String::from),
+					action: event.action,
+					data: event.data,
+				})
+				.collect(),
+		};
+		(
+			entity,
+			"batch".to_string(),
+			serde_json::to_value(batch).ok(),
+		)
+	};
+
+	let event = HubEvent {
+		hub: "Model".to_string(),
+		topic: entity,
+		label: Some(label),
+		seq: None,
+		request_id,
+		data,
+		target,
+	};
+	let event = ctx.get_model_manager().event_log().append(event);
+
+	ctx.emit_hub_event(event);
+}
+
+/// `EventTarget::Project` when all of the events are of the same project, otherwise all of the windows.
+fn group_target(group: &[PendingEvent]) -> EventTarget {
+	let mut project_ids = group.iter().map(event_project_id);
+	match project_ids.next().flatten() {
+		Some(first) if project_ids.all(|id| id == Some(first)) => {
+			EventTarget::Project(first.to_string())
+		}
+		_ => EventTarget::All,
+	}
+}
+
+/// The request id of the events, when the same for all of them.
+fn group_request_id(group: &[PendingEvent]) -> Option<String> {
+	let first = group.first()?.ctx.request_id()?;
+	group
+		.iter()
+		.all(|event| event.ctx.request_id() == Some(first))
+		.then(|| first.to_string())
+}
+
+/// The project of the event, from its data (i.e., `ModelEventData`), if any.
+fn event_project_id(event: &PendingEvent) -> Option<&str> {
+	let data = event.data.as_ref()?;
+	if event.entity == ProjectId::TABLE {
+		return data.get("id")?.as_str();
+	}
+	data.get("entity")
+		.or_else(|| data.get("deleted"))?
+		.get("project_id")?
+		.as_str()
+}
+
+// endregion: --- Coalescer Utils
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_utils::{new_test_ctx_with_window, project_for_create};
+	use crate::model::ProjectBmc;
+	use serde_json::json;
+
+	fn pending(ctx: &Arc<Ctx>, entity: &str, action: &str) -> PendingEvent {
+		PendingEvent {
+			ctx: ctx.clone(),
+			entity: entity.to_string(),
+			action: action.to_string(),
+			data: None,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_coalescer_group_by_entity() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, _events) = new_test_ctx_with_window(Duration::ZERO).await?;
+		let other_ctx = ctx.with_window_label("project-a");
+		let events = vec![
+			pending(&ctx, "project", "create"),
+			pending(&ctx, "task", "create"),
+			pending(&ctx.with_request_id("req-1"), "task", "create"),
+			pending(&ctx, "task", "update"),
+			pending(&other_ctx, "task", "update"),
+			pending(&ctx, "project", "update"),
+		];
+
+		// --- EXEC
+		let groups = group_by_entity(events);
+
+		// --- CHECK
+		let groups: Vec<Vec<(&str, &str)>> = groups
+			.iter()
+			.map(|g| {
+				g.iter()
+					.map(|e| (e.entity.as_str(), e.action.as_str()))
+					.collect()
+			})
+			.collect();
+		assert_eq!(
+			groups,
+			vec![
+				vec![("project", "create")],
+				vec![("task", "create"), ("task", "create"), ("task", "update")],
+				vec![("task", "update")],
+				vec![("project", "update")],
+			]
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_coalescer_group_target() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, _events) = new_test_ctx_with_window(Duration::ZERO).await?;
+		let task = |project_id: &str| PendingEvent {
+			data: Some(json!({"id": "task:1", "entity": {"project_id": project_id}})),
+			..pending(&ctx, "task", "update")
+		};
+		let project = PendingEvent {
+			data: Some(json!({"id": "project:a", "deleted": {"name": "A"}})),
+			..pending(&ctx, "project", "delete")
+		};
+		let many = PendingEvent {
+			data: Some(json!({"ids": ["task:1"]})),
+			..pending(&ctx, "task", "update_many")
+		};
+
+		// --- EXEC & CHECK
+		let project_a = EventTarget::Project("project:a".to_string());
+		assert_eq!(
+			group_target(&[task("project:a"), task("project:a")]),
+			project_a
+		);
+		assert_eq!(group_target(&[project]), project_a);
+		assert_eq!(
+			group_target(&[task("project:a"), task("project:b")]),
+			EventTarget::All
+		);
+		assert_eq!(group_target(&[task("project:a"), many]), EventTarget::All);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_coalescer_burst_batch() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, events) = new_test_ctx_with_window(Duration::from_millis(20)).await?;
+
+		// --- EXEC
+		for name in ["Project A", "Project B", "Project C"] {
+			ProjectBmc::create(ctx.clone(), project_for_create(name)).await?;
+		}
+
+		// --- CHECK
+		assert!(events.take("Model").is_empty(), "not before the window");
+		let evts = events.wait_for("Model").await;
+		assert_eq!(evts.len(), 1);
+		assert_eq!(evts[0].label.as_deref(), Some("batch"));
+		assert_eq!(evts[0].seq, Some(1));
+		let batch = &evts[0].data.as_ref().unwrap()["events"];
+		let names: Vec<_> = (0..3)
+			.map(|i| batch[i]["data"]["entity"]["name"].clone())
+			.collect();
+		assert_eq!(names, vec!["Project A", "Project B", "Project C"]);
+		assert_eq!(batch[0]["action"], "create");
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_coalescer_request_id() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, events) = new_test_ctx_with_window(Duration::from_millis(20)).await?;
+
+		// --- EXEC & CHECK - single event
+		ProjectBmc::create(
+			ctx.with_request_id("req-1"),
+			project_for_create("Project A"),
+		)
+		.await?;
+		let evts = events.wait_for("Model").await;
+		assert_eq!(evts[0].request_id.as_deref(), Some("req-1"));
+
+		// --- EXEC & CHECK - batch of two requests
+		ProjectBmc::create(
+			ctx.with_request_id("req-2"),
+			project_for_create("Project B"),
+		)
+		.await?;
+		ProjectBmc::create(
+			ctx.with_request_id("req-3"),
+			project_for_create("Project C"),
+		)
+		.await?;
+		let evts = events.wait_for("Model").await;
+		assert_eq!(evts[0].label.as_deref(), Some("batch"));
+		assert_eq!(evts[0].request_id, None);
+		let batch = &evts[0].data.as_ref().unwrap()["events"];
+		assert_eq!(batch[0]["request_id"], "req-2");
+		assert_eq!(batch[1]["request_id"], "req-3");
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -0,0 +1,522 @@
+//! Bounded log of the `Model` events, numbered with a sequence (i.e., `HubEvent.seq`), so the frontend can
+//! catch up on the events it missed (e.g., webview reload) with `events_since`.
+//!
+//! Notes:
+//!   - The sequence is app wide (i.e., not per workspace), and increases by one per `Model` event.
+//!   - The last `EVENT_LOG_MAX` events are kept in memory (what `events_since` returns), and appended
+//!     to the `event_log.jsonl` file of the app data dir (by the `EventLogWriter` listener), to be reloaded on start,
+//!     so the sequence continues after a restart.
+//!   - The file is one JSON event per line, rewritten with only the last events once in a while (see `prune`).
+//!     A line which cannot be read (e.g., partly written on a crash) is skipped.
+//!   - `events_since` only returns the events emitted to the window of the caller (see `EventTarget::is_for_window`).
+//!   - When the gap is too large (or the seq is unknown, e.g., of another app data dir),
+//!     `events_since` returns `resync: true` without events, and the frontend has to reload its data.
+//!
+
+use crate::ctx::Ctx;
+use crate::event::{AsyncHubListener, BoxFuture, EventTarget, HubEvent};
+use crate::window::ProjectWindows;
+use crate::Result;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use ts_rs::TS;
+
+/// Number of the last events kept (in memory and in the file).
+pub const EVENT_LOG_MAX: usize = 1000;
+
+/// File of the written events, in the app data dir.
+const FILE_NAME: &str = "event_log.jsonl";
+
+// region:    --- EventsSinceData
+
+#[derive(TS, Serialize)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct EventsSinceData<E: Serialize> {
+	/// The events after the given seq (in order), empty when `resync`
+	pub events: Vec<E>,
+	/// The missed events are not in the log anymore, the data has to be reloaded
+	pub resync: bool,
+	/// The seq of the last event
+	#[ts(type = "number")]
+	pub seq: u64,
+}
+
+// endregion: --- EventsSinceData
+
+// region:    --- EventLog
+
+/// The in memory log (held by the `ModelStore`), and its file.
+pub struct EventLog {
+	inner: Mutex<EventLogInner>,
+	path: PathBuf,
+	/// Serializes the file appends and rewrites (see `EventLogWriter`).
+	file_lock: Mutex<()>,
+}
+
+struct EventLogInner {
+	last_seq: u64,
+	events: VecDeque<HubEvent<JsonValue>>,
+}
+
+impl EventLog {
+	/// Load the last written events of the file of the app data dir (none when no file yet),
+	/// the sequence continuing from the last one.
+	///
+	/// Note: Blocking (std::fs), only called on start (see `ModelStore::new`).
+	pub(in crate::model) fn load(app_data_dir: &Path) -> Result<Self> {
+		let path = app_data_dir.join(FILE_NAME);
+		let events: VecDeque<_> = read_records(&path)?
+			.into_iter()
+			.map(HubEvent::from)
+			.collect();
+		let last_seq = events.back().and_then(|evt| evt.seq).unwrap_or(0);
+
+		Ok(EventLog {
+			inner: Mutex::new(EventLogInner { last_seq, events }),
+			path,
+			file_lock: Mutex::new(()),
+		})
+	}
+
+	/// Set the next seq on the event, and keep it (dropping the oldest one when full).
+	pub(in crate::model) fn append(&self, mut event: HubEvent<JsonValue>) -> HubEvent<JsonValue> {
+		let mut inner = self.inner.lock();
+		inner.last_seq += 1;
+		event.seq = Some(inner.last_seq);
+
+		if inner.events.len() == EVENT_LOG_MAX {
+			inner.events.pop_front();
+		}
+		inner.events.push_back(event.clone());
+
+		event
+	}
+
+	/// The events after the seq, of the window of the label (all of them when none, e.g., headless).
+	fn since(
+		&self,
+		seq: u64,
+		window_label: Option<&str>,
+		project_windows: &ProjectWindows,
+	) -> EventsSinceData<HubEvent<JsonValue>> {
+		let inner = self.inner.lock();
+		let first_seq = inner
+			.events
+			.front()
+			.and_then(|evt| evt.seq)
+			.unwrap_or(inner.last_seq + 1);
+
+		// Note: `seq > last_seq` when the seq is not of this log (e.g., of another app data dir),
+		//       `seq + 1 < first_seq` when the next event is not in the log anymore.
+		if seq > inner.last_seq || seq.saturating_add(1) < first_seq {
+			return EventsSinceData {
+				events: Vec::new(),
+				resync: true,
+				seq: inner.last_seq,
+			};
+		}
+
+		let events = inner
+			.events
+			.iter()
+			.filter(|evt| evt.seq.map(|s| s > seq).unwrap_or(false))
+			.filter(|evt| match window_label {
+				Some(label) => evt.target.is_for_window(label, project_windows),
+				None => true,
+			})
+			.cloned()
+			.collect();
+
+		EventsSinceData {
+			events,
+			resync: false,
+			seq: inner.last_seq,
+		}
+	}
+
+	/// Append the line (i.e., a written event) to the file.
+	fn write_line(&self, line: &str) -> io::Result<()> {
+		let _file_guard = self.file_lock.lock();
+		if let Some(dir) = self.path.parent() {
+			fs::create_dir_all(dir)?;
+		}
+		let mut file = OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(&self.path)?;
+		writeln!(file, "{line}")
+	}
+
+	/// Rewrite the file with only its last `EVENT_LOG_MAX` events (written to a temp file, then renamed).
+	fn prune(&self) -> Result<()> {
+		let _file_guard = self.file_lock.lock();
+		let records = read_records(&self.path)?;
+
+		let tmp_path = self.path.with_extension("jsonl.tmp");
+		let mut content = String::new();
+		for rec in records.iter() {
+			content.push_str(&serde_json::to_string(rec)?);
+			content.push('\n');
+		}
+		fs::write(&tmp_path, content)?;
+		fs::rename(&tmp_path, &self.path)?;
+
+		Ok(())
+	}
+}
+
+/// The last `EVENT_LOG_MAX` events of the file, by seq (the lines not readable are skipped).
+fn read_records(path: &Path) -> Result<Vec<EventLogRecord>> {
+	let content = match fs::read_to_string(path) {
+		Ok(content) => content,
+		Err(ex) if ex.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+		Err(ex) => return Err(ex.into()),
+	};
+
+	let mut records: Vec<EventLogRecord> = Vec::new();
+	for line in content.lines().filter(|line| !line.trim().is_empty()) {
+		match serde_json::from_str(line) {
+			Ok(rec) => records.push(rec),
+			Err(ex) => tracing::warn!(error = %ex, "event log line skipped"),
+		}
+	}
+
+	// Note: The lines can be out of order (the writes are spawned), or twice (a write during a prune).
+	records.sort_by_key(|rec| rec.seq);
+	records.dedup_by_key(|rec| rec.seq);
+	let skip = records.len().saturating_sub(EVENT_LOG_MAX);
+
+	Ok(records.into_iter().skip(skip).collect())
+}
+
+// endregion: --- EventLog
+
+// region:    --- EventLogBmc
+
+pub struct EventLogBmc;
+
+impl EventLogBmc {
+	/// The `Model` events after the seq (i.e., the last seq received by the caller), emitted to the window of the caller
+	/// (see `window::ProjectWindows`).
+	pub async fn events_since(
+		ctx: Arc<Ctx>,
+		seq: u64,
+		project_windows: &ProjectWindows,
+	) -> Result<EventsSinceData<HubEvent<JsonValue>>> {
+		let model_manager = ctx.get_model_manager();
+		let event_log = model_manager.event_log();
+		Ok(event_log.since(seq, ctx.window_label(), project_windows))
+	}
+}
+
+// endregion: --- EventLogBmc
+
+// region:    --- EventLogWriter
+
+/// Event bus listener writing the numbered events (i.e., the `Model` events), and pruning the old ones.
+pub struct EventLogWriter;
+
+impl AsyncHubListener for EventLogWriter {
+	fn on_event(&self, ctx: Arc<Ctx>, event: Arc<HubEvent<JsonValue>>) -> BoxFuture {
+		Box::pin(async move {
+			let seq = match event.seq {
+				Some(seq) => seq,
+				None => return,
+			};
+			let line = match serde_json::to_string(&EventLogRecord::new(&event, &ctx)) {
+				Ok(line) => line,
+				Err(err) => {
+					tracing::warn!(seq, error = %err, "event log write failed");
+					return;
+				}
+			};
+			let model_manager = ctx.get_model_manager();
+
+			let res = tokio::task::spawn_blocking(move || {
+				let event_log = model_manager.event_log();
+				if let Err(err) = event_log.write_line(&line) {
+					tracing::warn!(seq, error = %err, "event log write failed");
+				}
+
+				// Note: Prune once in a while rather than on each event.
+				if seq % 100 == 0 && seq > EVENT_LOG_MAX as u64 {
+					if let Err(err) = event_log.prune() {
+						tracing::warn!(seq, error = %err, "event log prune failed");
+					}
+				}
+			})
+			.await;
+			if let Err(err) = res {
+				tracing::warn!(seq, error = %err, "event log write failed");
+			}
+		})
+	}
+}
+
+/// The written event (i.e., a line of the file).
+#[derive(Serialize, Deserialize)]
+struct EventLogRecord {
+	seq: u64,
+	hub: String,
+	topic: String,
+	label: Option<String>,
+	request_id: Option<String>,
+	data: Option<JsonValue>,
+	target: Option<String>,
+}
+
+impl EventLogRecord {
+	/// Note: The target is resolved with the ctx of the event (i.e., `EventTarget::Origin` stored as its window).
+	fn new(event: &HubEvent<JsonValue>, ctx: &Ctx) -> Self {
+		EventLogRecord {
+			seq: event.seq.unwrap_or(0),
+			hub: event.hub.clone(),
+			topic: event.topic.clone(),
+			label: event.label.clone(),
+			request_id: event.request_id.clone(),
+			data: event.data.clone(),
+			target: Some(target_to_string(&event.target.resolve(ctx))),
+		}
+	}
+}
+
+impl From<EventLogRecord> for HubEvent<JsonValue> {
+	fn from(rec: EventLogRecord) -> Self {
+		HubEvent {
+			hub: rec.hub,
+			topic: rec.topic,
+			label: rec.label,
+			seq: Some(rec.seq),
+			request_id: rec.request_id,
+			data: rec.data,
+			target: rec
+				.target
+				.as_deref()
+				.map(target_from_str)
+				.unwrap_or_default(),
+		}
+	}
+}
+
+/// The stored form of the target (e.g., `all`, `window:main`, `project:project:xz4u..`).
+fn target_to_string(target: &EventTarget) -> String {
+	match target {
+		EventTarget::All | EventTarget::Origin => "all".to_string(),
+		EventTarget::Window(label) => format!("window:{label}"),
+		EventTarget::Project(project_id) => format!("project:{project_id}"),
+	}
+}
+
+/// The target of its stored form (see `target_to_string`), all windows when unknown.
+fn target_from_str(target: &str) -> EventTarget {
+	match target.split_once(':') {
+		Some(("window", label)) => EventTarget::Window(label.to_string()),
+		Some(("project", project_id)) => EventTarget::Project(project_id.to_string()),
+		_ => EventTarget::All,
+	}
+}
+
+// endregion: --- EventLogWriter
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_utils::{new_test_ctx, project_for_create};
+	use crate::model::ProjectBmc;
+	use std::time::Duration;
+
+	fn new_log() -> EventLog {
+		EventLog {
+			inner: Mutex::new(EventLogInner {
+				last_seq: 0,
+				events: VecDeque::new(),
+			}),
+			path: PathBuf::from(FILE_NAME),
+			file_lock: Mutex::new(()),
+		}
+	}
+
+	fn event(topic: &str) -> HubEvent<JsonValue> {
+		HubEvent {
+			hub: "Model".to_string(),
+			topic: topic.to_string(),
+			label: Some("create".to_string()),
+			seq: None,
+			request_id: None,
+			data: None,
+			target: EventTarget::All,
+		}
+	}
+
+	fn seqs(since: &EventsSinceData<HubEvent<JsonValue>>) -> Vec<Option<u64>> {
+		since.events.iter().map(|evt| evt.seq).collect()
+	}
+
+	#[test]
+	fn test_event_log_since() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let log = new_log();
+		let project_windows = ProjectWindows::default();
+		for topic in ["project", "task", "task"] {
+			log.append(event(topic));
+		}
+
+		// --- EXEC & CHECK
+		let since = log.since(1, None, &project_windows);
+		assert!(!since.resync);
+		assert_eq!(since.seq, 3);
+		assert_eq!(seqs(&since), vec![Some(2), Some(3)]);
+
+		assert_eq!(log.since(0, None, &project_windows).events.len(), 3);
+		assert!(log.since(3, None, &project_windows).events.is_empty());
+		assert!(
+			log.since(4, None, &project_windows).resync,
+			"seq not of the log"
+		);
+		assert!(
+			log.since(u64::MAX, None, &project_windows).resync,
+			"seq not of the log"
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_event_log_since_of_window() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let log = new_log();
+		let project_windows = ProjectWindows::default();
+		let targets = [
+			EventTarget::All,
+			EventTarget::Window("main".to_string()),
+			EventTarget::Window("other".to_string()),
+			EventTarget::Project("project:a".to_string()),
+		];
+		for target in targets {
+			log.append(HubEvent {
+				target,
+				..event("task")
+			});
+		}
+
+		// --- EXEC & CHECK - the events emitted to the window (not a project window)
+		let since = log.since(0, Some("main"), &project_windows);
+		assert_eq!(since.seq, 4);
+		assert_eq!(seqs(&since), vec![Some(1), Some(2), Some(4)]);
+
+		// --- EXEC & CHECK - headless, all of the events
+		assert_eq!(log.since(0, None, &project_windows).events.len(), 4);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_event_log_since_gap_too_large() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let log = new_log();
+		let project_windows = ProjectWindows::default();
+		for _ in 0..EVENT_LOG_MAX + 5 {
+			log.append(event("task"));
+		}
+
+		// --- EXEC & CHECK
+		let since = log.since(2, None, &project_windows);
+		assert!(since.resync);
+		assert!(since.events.is_empty());
+		assert!(
+			!log.since(5, None, &project_windows).resync,
+			"next event (seq 6) still in the log"
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_event_log_file_load_and_prune() -> anyhow::Result<()> {
+		// --- FIXTURE - lines out of order, twice, and a partly written one
+		let dir = tempfile::tempdir()?;
+		let log = EventLog::load(dir.path())?;
+		for seq in (1..=EVENT_LOG_MAX as u64 + 10).rev() {
+			let rec = EventLogRecord {
+				seq,
+				hub: "Model".to_string(),
+				topic: "task".to_string(),
+				label: Some("create".to_string()),
+				request_id: None,
+				data: Some(serde_json::json!({ "id": seq })),
+				target: Some("window:main".to_string()),
+			};
+			log.write_line(&serde_json::to_string(&rec)?)?;
+		}
+		log.write_line(r#"{"seq": 1"#)?;
+		log.write_line(&serde_json::to_string(&EventLogRecord {
+			seq: 12,
+			..read_records(&log.path)?.remove(1)
+		})?)?;
+
+		// --- EXEC
+		let loaded = EventLog::load(dir.path())?;
+
+		// --- CHECK - the last events, in order
+		let since = loaded.since(10, None, &ProjectWindows::default());
+		assert!(!since.resync);
+		assert_eq!(since.seq, EVENT_LOG_MAX as u64 + 10);
+		assert_eq!(since.events.len(), EVENT_LOG_MAX);
+		assert_eq!(since.events[0].seq, Some(11));
+		assert_eq!(since.events[0].data, Some(serde_json::json!({ "id": 11 })));
+		assert_eq!(
+			since.events[0].target,
+			EventTarget::Window("main".to_string())
+		);
+
+		// --- EXEC & CHECK - prune, only the last events in the file
+		log.prune()?;
+		let content = fs::read_to_string(&log.path)?;
+		assert_eq!(content.lines().count(), EVENT_LOG_MAX);
+		assert!(!dir.path().join("event_log.jsonl.tmp").exists());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_event_log_reloaded() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, events) = new_test_ctx().await?;
+		let data = project_for_create("Project 01");
+
+		// --- EXEC
+		let id = ProjectBmc::create(ctx.clone(), data).await?.id;
+
+		// --- CHECK - numbered event
+		let evts = events.take("Model");
+		assert_eq!(evts[0].seq, Some(1));
+
+		// --- CHECK - reloaded from the file (once written by the listener)
+		let model_manager = ctx.get_model_manager();
+		let app_data_dir = model_manager.event_log().path.parent().unwrap();
+		let mut loaded = Vec::new();
+		for _ in 0..100 {
+			loaded = EventLog::load(app_data_dir)?
+				.since(0, None, &ProjectWindows::default())
+				.events;
+			if !loaded.is_empty() {
+				break;
+			}
+			tokio::time::sleep(Duration::from_millis(10)).await;
+		}
+		assert_eq!(loaded.len(), 1);
+		assert_eq!(loaded[0].seq, Some(1));
+		assert_eq!(loaded[0].data.as_ref().unwrap()["id"], id.as_str());
+		assert_eq!(loaded[0].target, EventTarget::Project(id.clone()));
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
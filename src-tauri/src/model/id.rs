@@ -0,0 +1,128 @@
+//! Typed record ids (e.g., `ProjectId`), checking the table of the id (i.e., `project:..`) when deserialized,
+//! so a task id given for a project fails at the IPC params (rather than in the Backend Model Controller).
+//!
+//! Notes:
+//!   - The ids are stored and serialized as the record id strings (e.g., `project:abc`).
+//!   - In TypeScript, they are branded string types (e.g., `string & { readonly __brand: "ProjectId" }`),
+//!     so they can be used as strings, but a string (or a `TaskId`) cannot be given for a `ProjectId`.
+//!   - They deref to `str`, so they can be given to the functions taking a `&str` id.
+//!
+
+use crate::prelude::*;
+use serde::{de, Deserialize, Deserializer, Serialize};
+use surrealdb::sql::{thing, Value};
+use ts_rs::{Dependency, TS};
+
+macro_rules! typed_id {
+	($name:ident, $tb:literal) => {
+		#[derive(Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+		#[serde(transparent)]
+		pub struct $name(String);
+
+		impl $name {
+			pub const TABLE: &'static str = $tb;
+
+			/// For the ids from the store (e.g., of a created record), which are of the table.
+			#[allow(dead_code)] // not used for all of the id types
+			pub(in crate::model) fn from_store(id: String) -> Self {
+				Self(id)
+			}
+		}
+
+		impl<'de> Deserialize<'de> for $name {
+			fn deserialize<D: Deserializer<'de>>(
+				deserializer: D,
+			) -> core::result::Result<Self, D::Error> {
+				let id = String::deserialize(deserializer)?;
+				if is_of_table(&id, $tb) {
+					Ok(Self(id))
+				} else {
+					Err(de::Error::custom(f!("not a {} id: {id}", $tb)))
+				}
+			}
+		}
+
+		impl std::ops::Deref for $name {
+			type Target = str;
+			fn deref(&self) -> &str {
+				&self.0
+			}
+		}
+
+		impl std::fmt::Display for $name {
+			fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+				f.write_str(&self.0)
+			}
+		}
+
+		impl From<$name> for String {
+			fn from(val: $name) -> Self {
+				val.0
+			}
+		}
+
+		impl From<$name> for Value {
+			fn from(val: $name) -> Self {
+				val.0.into()
+			}
+		}
+
+		impl TS for $name {
+			const EXPORT_TO: Option<&'static str> =
+				Some(concat!("../src-ui/src/bindings/", stringify!($name), ".ts"));
+
+			fn decl() -> String {
+				f!("type {} = {};", Self::name(), Self::inline())
+			}
+			fn name() -> String {
+				stringify!($name).to_string()
+			}
+			fn inline() -> String {
+				f!("string & {{ readonly __brand: \"{}\" }}", Self::name())
+			}
+			fn dependencies() -> Vec<Dependency> {
+				vec![]
+			}
+			fn transparent() -> bool {
+				false
+			}
+		}
+	};
+}
+
+typed_id!(ProjectId, "project");
+typed_id!(TaskId, "task");
+
+/// If the id is a record id (e.g., `project:abc`) of the table.
+fn is_of_table(id: &str, tb: &str) -> bool {
+	thing(id).map(|th| th.tb == tb).unwrap_or(false)
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn export_bindings_typed_ids() -> anyhow::Result<()> {
+		ProjectId::export()?;
+		TaskId::export()?;
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_typed_id_deserialize() -> anyhow::Result<()> {
+		// --- EXEC & CHECK
+		let id: ProjectId = serde_json::from_value("project:abc".into())?;
+		assert_eq!(&*id, "project:abc");
+		assert_eq!(serde_json::to_value(&id)?, "project:abc");
+
+		assert!(serde_json::from_value::<ProjectId>("task:abc".into()).is_err());
+		assert!(serde_json::from_value::<ProjectId>("abc".into()).is_err());
+		assert!(serde_json::from_value::<TaskId>("task:abc".into()).is_ok());
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -6,37 +6,115 @@
 //!
 
 use crate::ctx::Ctx;
-use crate::event::HubEvent;
 use serde::Serialize;
+use serde_with_macros::skip_serializing_none;
+use std::sync::Arc;
 use store::SurrealStore;
 use ts_rs::TS;
 
+mod attachment;
 mod bmc_base;
+mod comment;
+mod event_coalescer;
+mod event_log;
+mod id;
 mod model_store;
 mod project;
+mod recur;
 mod seed_for_dev;
 mod store;
+mod subscription;
 mod task;
+mod task_dep;
+#[cfg(test)]
+pub(crate) mod test_utils;
+mod time_entry;
+mod validate;
+mod workspace;
 
 // --- Re-exports
+pub use attachment::*;
+pub use comment::*;
+pub use event_log::*;
+pub use id::{ProjectId, TaskId};
 pub use model_store::*;
 pub use project::*;
+pub use subscription::*;
 pub use task::*;
+pub use task_dep::*;
+pub use time_entry::*;
+pub use validate::FieldViolation;
+pub use workspace::*;
 // For dev only
-pub use seed_for_dev::seed_store_for_dev;
+pub use seed_for_dev::{is_store_empty, seed_store_for_dev};
 
 // region:    --- Model Event
 
-fn fire_model_event<D>(ctx: &Ctx, entity: &str, action: &str, data: D)
+/// Note: The event is emitted by the coalescer (see `event_coalescer`), after its window.
+fn fire_model_event<D>(ctx: &Arc<Ctx>, entity: &str, action: &str, data: D)
 where
 	D: Serialize + Clone,
 {
-	ctx.emit_hub_event(HubEvent {
-		hub: "Model".to_string(),
-		topic: entity.to_string(),
-		label: Some(action.to_string()),
-		data: Some(data),
-	});
+	let data = serde_json::to_value(data).ok();
+	ctx.get_model_manager()
+		.event_coalescer()
+		.push(ctx, entity, action, data);
+}
+
+/// Data of the `Model` hub events (topic: entity, label: `create`, `update`, or `delete`),
+/// so the listeners do not have to get the entity back.
+///
+/// Note: The `update_many` events have a `ModelManyEventData` (i.e., one `ModelEventData` per entity),
+///       and the `delete_many` ones only have the `ModelMutateManyResultData`.
+#[skip_serializing_none]
+#[derive(TS, Serialize, Clone)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct ModelEventData<E: Serialize + Clone> {
+	pub id: String,
+	/// The entity after the mutation (`create`, `update`)
+	pub entity: Option<E>,
+	/// The properties changed by the mutation (`update`)
+	pub changed: Option<Vec<String>>,
+	/// The entity before its deletion (`delete`)
+	pub deleted: Option<E>,
+}
+
+impl<E: Serialize + Clone> ModelEventData<E> {
+	fn created(id: String, entity: E) -> Self {
+		Self {
+			id,
+			entity: Some(entity),
+			changed: None,
+			deleted: None,
+		}
+	}
+
+	fn updated(id: String, entity: E, changed: Vec<String>) -> Self {
+		Self {
+			id,
+			entity: Some(entity),
+			changed: Some(changed),
+			deleted: None,
+		}
+	}
+
+	fn deleted(id: String, deleted: E) -> Self {
+		Self {
+			id,
+			entity: None,
+			changed: None,
+			deleted: Some(deleted),
+		}
+	}
+}
+
+/// Data of the `Model` bulk update events (label: `update_many`), with the data of each updated entity.
+#[derive(TS, Serialize, Clone)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct ModelManyEventData<E: Serialize + Clone> {
+	pub ids: Vec<String>,
+	/// The `update` data of each entity (in the `ids` order)
+	pub items: Vec<ModelEventData<E>>,
 }
 
 // endregion: --- Model Event
@@ -57,6 +135,19 @@ impl From<String> for ModelMutateResultData {
 	}
 }
 
+/// Result of the bulk mutations (e.g., `TaskBmc::update_many`)
+#[derive(TS, Serialize, Clone)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct ModelMutateManyResultData {
+	pub ids: Vec<String>,
+}
+
+impl From<Vec<String>> for ModelMutateManyResultData {
+	fn from(ids: Vec<String>) -> Self {
+		Self { ids }
+	}
+}
+
 // endregion: --- Common Model Result Data
 
 // region:    --- Tests
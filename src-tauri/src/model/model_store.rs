@@ -4,18 +4,131 @@
 //!   1) Expose only the "new" to outside the model module tree.
 //!   2) Access to the underlying store is allowed only for the model module tree.
 
+use super::event_coalescer::{EventCoalescer, DEFAULT_EVENT_WINDOW};
+use super::event_log::EventLog;
+use super::store::BlobStore;
+use super::subscription::ListSubscriptions;
+use super::workspace::init_workspaces;
 use super::SurrealStore;
+use crate::ctx::Ctx;
 use crate::Result;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::{Mutex, MutexGuard};
 
-pub struct ModelStore(SurrealStore);
+/// Database of the workspace registry (see `model::workspace`)
+const META_DB: &str = "appmeta";
+
+pub struct ModelStore {
+	store: SurrealStore,
+	meta_store: SurrealStore,
+	attachments_dir: PathBuf,
+	subscriptions: ListSubscriptions,
+	event_log: EventLog,
+	event_coalescer: EventCoalescer,
+	/// Serializes the blob writes with their records, and the blob gc (see `AttachmentBmc`).
+	blob_lock: Mutex<()>,
+	/// Serializes the timer start and stop (see `TimeEntryBmc`).
+	timer_lock: Mutex<()>,
+	/// Serializes the task dependency creates (see `TaskDepBmc`).
+	task_dep_lock: Mutex<()>,
+	/// Serializes the workspace switches and deletes (see `WorkspaceBmc`).
+	workspace_lock: Mutex<()>,
+}
 
 impl ModelStore {
-	/// Create a new ModelStore instance and its corresponding SurrealStore
-	pub async fn new() -> Result<Self> {
-		Ok(ModelStore(SurrealStore::new().await?))
+	/// Create a new ModelStore instance and its corresponding SurrealStore.
+	/// Files (e.g., attachments, the event log) are stored under `app_data_dir`, and the data in the datastore of `store_path`
+	/// (e.g., `memory`).
+	pub async fn new(app_data_dir: &Path, store_path: &str) -> Result<Self> {
+		let store = SurrealStore::new(store_path).await?;
+		let meta_store = store.with_db(META_DB)?;
+		init_workspaces(&meta_store).await?;
+		let event_log = EventLog::load(app_data_dir)?;
+
+		Ok(ModelStore {
+			store,
+			meta_store,
+			attachments_dir: app_data_dir.join("attachments"),
+			subscriptions: ListSubscriptions::default(),
+			event_log,
+			event_coalescer: EventCoalescer::new(DEFAULT_EVENT_WINDOW),
+			blob_lock: Mutex::new(()),
+			timer_lock: Mutex::new(()),
+			task_dep_lock: Mutex::new(()),
+			workspace_lock: Mutex::new(()),
+		})
+	}
+
+	/// The window to coalesce the `Model` events (see `model::event_coalescer`), zero to emit them right away.
+	pub fn with_event_window(mut self, window: Duration) -> Self {
+		self.event_coalescer = EventCoalescer::new(window);
+		self
+	}
+
+	/// The store of the workspace of the ctx (i.e., active when the ctx was created, see `Ctx::workspace_db`),
+	/// so all of the store calls of an operation are on the same database, even if the workspace is switched meanwhile.
+	pub(in crate::model) fn store(&self, ctx: &Ctx) -> SurrealStore {
+		self.store.on_db(ctx.workspace_db())
+	}
+
+	/// The store of the active workspace (e.g., to switch it, or to seed it on start).
+	pub(in crate::model) fn active_store(&self) -> &SurrealStore {
+		&self.store
+	}
+
+	/// The database name of the active workspace.
+	pub fn active_db(&self) -> String {
+		self.store.db()
+	}
+
+	/// The store of the workspace registry.
+	pub(in crate::model) fn meta_store(&self) -> &SurrealStore {
+		&self.meta_store
+	}
+
+	/// The attachment blob store of the workspace of the ctx (see `store`).
+	pub(in crate::model) fn blob_store(&self, ctx: &Ctx) -> BlobStore {
+		BlobStore::new(self.blob_dir(ctx.workspace_db()))
+	}
+
+	/// Held from the blob write to its attachment record create, and by the blob gc, so the gc does not
+	/// delete the blob of an attachment being created.
+	pub(in crate::model) async fn lock_blobs(&self) -> MutexGuard<'_, ()> {
+		self.blob_lock.lock().await
+	}
+
+	/// Held from the running timer check to the timer create (or stop), so only one timer can run.
+	pub(in crate::model) async fn lock_timer(&self) -> MutexGuard<'_, ()> {
+		self.timer_lock.lock().await
+	}
+
+	/// Held from the duplicate and cycle checks to the dependency create, so two creates cannot add a cycle together.
+	pub(in crate::model) async fn lock_task_deps(&self) -> MutexGuard<'_, ()> {
+		self.task_dep_lock.lock().await
+	}
+
+	/// Held from the active workspace check to the workspace delete (and by the switch),
+	/// so the workspace being switched to cannot be deleted.
+	pub(in crate::model) async fn lock_workspaces(&self) -> MutexGuard<'_, ()> {
+		self.workspace_lock.lock().await
+	}
+
+	/// The list subscriptions of the active workspace.
+	pub(in crate::model) fn subscriptions(&self) -> &ListSubscriptions {
+		&self.subscriptions
+	}
+
+	/// The log of the `Model` events (see `model::event_log`).
+	pub(in crate::model) fn event_log(&self) -> &EventLog {
+		&self.event_log
+	}
+
+	pub(in crate::model) fn event_coalescer(&self) -> &EventCoalescer {
+		&self.event_coalescer
 	}
 
-	pub(in crate::model) fn store(&self) -> &SurrealStore {
-		&self.0
+	pub(in crate::model) fn blob_dir(&self, db: &str) -> PathBuf {
+		self.attachments_dir.join(db)
 	}
 }
@@ -1,41 +1,43 @@
 //! All model and controller for the Project type
 //!
-use super::bmc_base::{bmc_create, bmc_delete, bmc_get, bmc_list, bmc_update};
-use super::store::{Creatable, Filterable, Patchable};
-use super::ModelMutateResultData;
+use super::bmc_base::{bmc_delete, bmc_list, bmc_update};
+use super::store::{from_object, new_tid, Creatable, Filterable, Nanos};
+use super::validate::{Validate, Violations, NAME_RULE, TEXT_RULE};
+use super::{
+	fire_model_event, ModelEventData, ModelMutateResultData, ProjectId, Task, TaskBmc, TaskFilter,
+	TaskTarget,
+};
 use crate::ctx::Ctx;
-use crate::utils::XTake;
 use crate::{Error, Result};
-use modql::filter::{FilterNodes, OpValsString};
+use app_macros::{Bmc, Creatable, Patchable};
+use modql::filter::{FilterNodes, OpValBool, OpValString, OpValsBool, OpValsString};
 use modql::ListOptions;
 use serde::{Deserialize, Serialize};
 use serde_with_macros::skip_serializing_none;
-use std::collections::BTreeMap;
 use std::sync::Arc;
-use surrealdb::sql::{Object, Value};
 use ts_rs::TS;
 
 // region:    --- Project
 
-#[derive(Serialize, TS, Debug)]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, TS, Debug, Clone)]
 #[ts(export, export_to = "../src-ui/src/bindings/")]
 pub struct Project {
-	pub id: String,
+	pub id: ProjectId,
 	pub name: String,
-	pub ctime: String,
-}
+	pub ctime: Nanos,
 
-impl TryFrom<Object> for Project {
-	type Error = Error;
-	fn try_from(mut val: Object) -> Result<Project> {
-		let project = Project {
-			id: val.x_take_val("id")?,
-			name: val.x_take_val("name")?,
-			ctime: val.x_take_val::<i64>("ctime")?.to_string(),
-		};
-
-		Ok(project)
-	}
+	pub description: Option<String>,
+	/// CSS color (e.g., `#3b82f6`)
+	pub color: Option<String>,
+	/// Icon key (i.e., svg symbol name)
+	pub icon: Option<String>,
+	/// Archived projects are hidden by default, and their tasks are read-only.
+	#[serde(default)]
+	pub archived: bool,
+	/// Templates are used to create new projects (see `ProjectBmc::create_from_template`).
+	#[serde(default)]
+	pub template: bool,
 }
 
 // endregion: --- Project
@@ -43,55 +45,82 @@ impl TryFrom<Object> for Project {
 // region:    --- ProjectForCreate
 
 #[skip_serializing_none]
-#[derive(Deserialize, TS, Debug)]
+#[derive(Serialize, Deserialize, TS, Debug, Creatable)]
 #[ts(export, export_to = "../src-ui/src/bindings/")]
 pub struct ProjectForCreate {
 	pub name: String,
+	pub description: Option<String>,
+	pub color: Option<String>,
+	pub icon: Option<String>,
+	#[store(default = false)]
+	pub archived: Option<bool>,
+	#[store(default = false)]
+	pub template: Option<bool>,
 }
 
-impl From<ProjectForCreate> for Value {
-	fn from(val: ProjectForCreate) -> Self {
-		BTreeMap::from([
-			// Note: could have used map![.. => ..] as well
-			("name".into(), val.name.into()),
-		])
-		.into()
+impl Validate for ProjectForCreate {
+	fn check(&self, violations: &mut Violations) {
+		violations.text("name", &self.name, NAME_RULE);
+		violations.opt_text("description", self.description.as_deref(), TEXT_RULE);
+		violations.opt_color("color", self.color.as_deref());
+		violations.opt_key("icon", self.icon.as_deref());
 	}
 }
 
-impl Creatable for ProjectForCreate {}
-
 // endregion: --- ProjectForCreate
 
 // region:    --- ProjectForUpdate
 
 #[skip_serializing_none]
-#[derive(Deserialize, TS, Debug)]
+#[derive(Serialize, Deserialize, TS, Debug, Patchable)]
 #[ts(export, export_to = "../src-ui/src/bindings/")]
 pub struct ProjectForUpdate {
 	pub name: Option<String>,
+	pub description: Option<String>,
+	pub color: Option<String>,
+	pub icon: Option<String>,
+	pub archived: Option<bool>,
+	pub template: Option<bool>,
 }
 
-impl From<ProjectForUpdate> for Value {
-	fn from(val: ProjectForUpdate) -> Self {
-		let mut data = BTreeMap::new();
-		if let Some(name) = val.name {
-			data.insert("name".into(), name.into());
-		}
-		data.into()
+impl Validate for ProjectForUpdate {
+	fn check(&self, violations: &mut Violations) {
+		violations.opt_text("name", self.name.as_deref(), NAME_RULE);
+		violations.opt_text("description", self.description.as_deref(), TEXT_RULE);
+		violations.opt_color("color", self.color.as_deref());
+		violations.opt_key("icon", self.icon.as_deref());
 	}
 }
 
-impl Patchable for ProjectForUpdate {}
-
 // endregion: --- ProjectForUpdate
 
+// region:    --- ProjectForClone
+
+/// Options of `ProjectBmc::clone_project` and `ProjectBmc::create_from_template`.
+#[skip_serializing_none]
+#[derive(Deserialize, TS, Debug, Default)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct ProjectForClone {
+	/// Name of the new project (default to the source name, with ` (copy)` when cloning)
+	pub name: Option<String>,
+	/// Mark all of the new tasks as not done (default false when cloning, true from a template)
+	pub reset_done: Option<bool>,
+	/// Do not copy the task descriptions (default false)
+	pub strip_desc: Option<bool>,
+	/// Mark the new project as a template (default false)
+	pub template: Option<bool>,
+}
+
+// endregion: --- ProjectForClone
+
 // region:    --- ProjectFilter
 
 #[derive(FilterNodes, Deserialize, Debug)]
 pub struct ProjectFilter {
 	pub id: Option<OpValsString>,
 	pub name: Option<OpValsString>,
+	pub archived: Option<OpValsBool>,
+	pub template: Option<OpValsBool>,
 }
 
 impl Filterable for ProjectFilter {}
@@ -100,34 +129,369 @@ impl Filterable for ProjectFilter {}
 
 // region:    --- ProjectBmc
 
+/// Note: `get`, `create`, `update` are generated (see `app_macros::Bmc`),
+///       with the CRUD commands (see `project_commands`).
+#[derive(Bmc)]
+#[bmc(
+	table = "project",
+	entity = Project,
+	for_create = ProjectForCreate,
+	for_update = ProjectForUpdate,
+	custom(delete, list),
+	ipc(id = ProjectId)
+)]
 pub struct ProjectBmc;
 
 impl ProjectBmc {
-	const ENTITY: &'static str = "project";
+	/// Note: Archived projects are not listed unless the filter has an `archived` condition.
+	pub async fn list(ctx: Arc<Ctx>, filter: Option<ProjectFilter>) -> Result<Vec<Project>> {
+		// Note: `!= true` rather than `= false` to match the projects without the archived property.
+		let not_archived = || Some(OpValBool::Not(true).into());
+		let filter = match filter {
+			Some(filter) if filter.archived.is_some() => filter,
+			Some(filter) => ProjectFilter {
+				archived: not_archived(),
+				..filter
+			},
+			None => ProjectFilter {
+				id: None,
+				name: None,
+				archived: not_archived(),
+				template: None,
+			},
+		};
 
-	pub async fn get(ctx: Arc<Ctx>, id: &str) -> Result<Project> {
-		bmc_get(ctx, Self::ENTITY, id).await
+		bmc_list(ctx, Self::ENTITY, Some(filter), ListOptions::default()).await
 	}
 
-	pub async fn create(ctx: Arc<Ctx>, data: ProjectForCreate) -> Result<ModelMutateResultData> {
-		bmc_create(ctx, Self::ENTITY, data).await
+	/// Delete the project with all of its tasks (and their dependencies, comments, ...),
+	/// with a `Model`/`task`/`delete_many` event for the tasks (see `TaskBmc::delete_many`).
+	///
+	/// Note: Archived projects have to be unarchived first, as their tasks are read-only.
+	pub async fn delete(ctx: Arc<Ctx>, id: &str) -> Result<ModelMutateResultData> {
+		Self::ensure_not_archived(ctx.clone(), id).await?;
+
+		let filter = TaskFilter {
+			project_id: Some(OpValString::Eq(id.to_string()).into()),
+			title: None,
+			done: None,
+		};
+		let task_ids: Vec<String> = TaskBmc::list(ctx.clone(), Some(filter))
+			.await?
+			.into_iter()
+			.map(|t| t.id.into())
+			.collect();
+		if !task_ids.is_empty() {
+			TaskBmc::delete_many(ctx.clone(), TaskTarget::Ids(task_ids)).await?;
+		}
+
+		bmc_delete::<Project>(ctx, Self::ENTITY, id).await
+	}
+
+	pub async fn archive(ctx: Arc<Ctx>, id: &str) -> Result<ModelMutateResultData> {
+		Self::set_archived(ctx, id, true).await
+	}
+
+	pub async fn unarchive(ctx: Arc<Ctx>, id: &str) -> Result<ModelMutateResultData> {
+		Self::set_archived(ctx, id, false).await
 	}
 
-	pub async fn update(
+	/// Copy a project with all of its tasks (new ids and ctimes), in one transaction.
+	/// The copy is never archived. Returns the id of the new project.
+	///
+	/// Note: Only the projects and tasks are copied (i.e., not the comments, attachments, time entries, dependencies).
+	pub async fn clone_project(
 		ctx: Arc<Ctx>,
 		id: &str,
-		data: ProjectForUpdate,
+		opts: ProjectForClone,
 	) -> Result<ModelMutateResultData> {
-		bmc_update(ctx, Self::ENTITY, id, data).await
+		let project = Self::get(ctx.clone(), id).await?;
+		let name = opts
+			.name
+			.unwrap_or_else(|| format!("{} (copy)", project.name));
+
+		Self::create_copy(
+			ctx,
+			project,
+			name,
+			opts.reset_done.unwrap_or(false),
+			opts.strip_desc.unwrap_or(false),
+			opts.template.unwrap_or(false),
+		)
+		.await
 	}
 
-	pub async fn delete(ctx: Arc<Ctx>, id: &str) -> Result<ModelMutateResultData> {
-		bmc_delete(ctx, Self::ENTITY, id).await
+	/// Create a new project (with its tasks) from a template project (see `clone_project`).
+	pub async fn create_from_template(
+		ctx: Arc<Ctx>,
+		template_id: &str,
+		opts: ProjectForClone,
+	) -> Result<ModelMutateResultData> {
+		let project = Self::get(ctx.clone(), template_id).await?;
+		if !project.template {
+			return Err(Error::ProjectNotTemplate(project.id.into()));
+		}
+		let name = opts.name.unwrap_or_else(|| project.name.clone());
+
+		Self::create_copy(
+			ctx,
+			project,
+			name,
+			opts.reset_done.unwrap_or(true),
+			opts.strip_desc.unwrap_or(false),
+			opts.template.unwrap_or(false),
+		)
+		.await
 	}
 
-	pub async fn list(ctx: Arc<Ctx>, filter: Option<ProjectFilter>) -> Result<Vec<Project>> {
-		bmc_list(ctx, Self::ENTITY, filter, ListOptions::default()).await
+	/// Fails with `Error::ProjectArchived` if the project is archived (i.e., its tasks are read-only).
+	pub async fn ensure_not_archived(ctx: Arc<Ctx>, id: &str) -> Result<()> {
+		if Self::get(ctx, id).await?.archived {
+			Err(Error::ProjectArchived(id.to_string()))
+		} else {
+			Ok(())
+		}
+	}
+
+	async fn create_copy(
+		ctx: Arc<Ctx>,
+		project: Project,
+		name: String,
+		reset_done: bool,
+		strip_desc: bool,
+		template: bool,
+	) -> Result<ModelMutateResultData> {
+		let data = ProjectForCreate {
+			name,
+			description: project.description,
+			color: project.color,
+			icon: project.icon,
+			archived: Some(false),
+			template: Some(template),
+		};
+		data.validate()?;
+		let data = data.to_object()?;
+
+		let new_id = new_tid(Self::ENTITY);
+		let mut records = vec![(new_id.clone(), data)];
+		records.extend(
+			TaskBmc::records_for_clone(ctx.clone(), &project.id, &new_id, reset_done, strip_desc)
+				.await?,
+		);
+
+		let model_manager = ctx.get_model_manager();
+		let store = model_manager.store(&ctx);
+		let ids = store.exec_create_all(records).await?;
+
+		let project: Project = from_object(store.exec_get(Self::ENTITY, &new_id).await?)?;
+		fire_model_event(
+			&ctx,
+			Self::ENTITY,
+			"create",
+			ModelEventData::created(new_id.clone(), project),
+		);
+		for obj in store.exec_get_many("task", &ids[1..]).await? {
+			let task: Task = from_object(obj)?;
+			fire_model_event(
+				&ctx,
+				"task",
+				"create",
+				ModelEventData::created(task.id.to_string(), task),
+			);
+		}
+
+		Ok(ModelMutateResultData::from(new_id))
+	}
+
+	async fn set_archived(
+		ctx: Arc<Ctx>,
+		id: &str,
+		archived: bool,
+	) -> Result<ModelMutateResultData> {
+		let data = ProjectForUpdate {
+			name: None,
+			description: None,
+			color: None,
+			icon: None,
+			archived: Some(archived),
+			template: None,
+		};
+		bmc_update::<Project, _>(ctx, Self::ENTITY, id, data).await
 	}
 }
 
 // endregion: --- ProjectBmc
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_utils::{new_test_ctx, project_for_create, task_for_create};
+	use crate::model::{CommentBmc, CommentForCreate, TaskId, TimeEntryBmc, TimerForStart};
+	use serde_json::json;
+
+	#[tokio::test]
+	async fn test_project_bmc_crud_events() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, events) = new_test_ctx().await?;
+
+		// --- EXEC & CHECK - create
+		let id = ProjectBmc::create(ctx.clone(), project_for_create("Project 01"))
+			.await?
+			.id;
+		let evts = events.take("Model");
+		assert_eq!(evts.len(), 1);
+		assert_eq!(evts[0].topic, "project");
+		assert_eq!(evts[0].label.as_deref(), Some("create"));
+		let data = evts[0].data.as_ref().unwrap();
+		assert_eq!(data["id"], json!(id));
+		assert_eq!(data["entity"]["name"], "Project 01");
+
+		// --- EXEC & CHECK - update
+		let data = ProjectForUpdate {
+			name: Some("Project 02".to_string()),
+			description: None,
+			color: None,
+			icon: None,
+			archived: None,
+			template: None,
+		};
+		ProjectBmc::update(ctx.clone(), &id, data).await?;
+		let evts = events.take("Model");
+		assert_eq!(evts[0].label.as_deref(), Some("update"));
+		let data = evts[0].data.as_ref().unwrap();
+		assert_eq!(data["entity"]["name"], "Project 02");
+		assert_eq!(data["changed"], json!(["name"]));
+
+		// --- EXEC & CHECK - delete
+		ProjectBmc::delete(ctx.clone(), &id).await?;
+		let evts = events.take("Model");
+		assert_eq!(evts[0].label.as_deref(), Some("delete"));
+		assert_eq!(
+			evts[0].data.as_ref().unwrap()["deleted"]["name"],
+			"Project 02"
+		);
+		assert!(matches!(
+			ProjectBmc::get(ctx, &id).await,
+			Err(Error::StoreRecordNotFound(_))
+		));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_project_bmc_list_archived() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, _events) = new_test_ctx().await?;
+		let id_a = ProjectBmc::create(ctx.clone(), project_for_create("Project A"))
+			.await?
+			.id;
+		ProjectBmc::create(ctx.clone(), project_for_create("Project B")).await?;
+
+		// --- EXEC
+		ProjectBmc::archive(ctx.clone(), &id_a).await?;
+
+		// --- CHECK
+		let projects = ProjectBmc::list(ctx.clone(), None).await?;
+		assert_eq!(projects.len(), 1);
+		assert_eq!(projects[0].name, "Project B");
+		let filter: ProjectFilter = serde_json::from_value(json!({"archived": true}))?;
+		let archived = ProjectBmc::list(ctx, Some(filter)).await?;
+		assert_eq!(archived.len(), 1);
+		assert_eq!(&*archived[0].id, id_a.as_str());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_project_bmc_delete_with_tasks() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, events) = new_test_ctx().await?;
+		let id = ProjectBmc::create(ctx.clone(), project_for_create("Project 01"))
+			.await?
+			.id;
+		let project_id = ProjectId::from_store(id.clone());
+		let other_id = ProjectBmc::create(ctx.clone(), project_for_create("Project 02"))
+			.await?
+			.id;
+		let task_id = TaskBmc::create(ctx.clone(), task_for_create(&project_id, "Task 01"))
+			.await?
+			.id;
+		let other_project_id = ProjectId::from_store(other_id);
+		TaskBmc::create(ctx.clone(), task_for_create(&other_project_id, "Task 02")).await?;
+		let comment_data = CommentForCreate {
+			task_id: TaskId::from_store(task_id.clone()),
+			body: "Comment 01".to_string(),
+		};
+		CommentBmc::create(ctx.clone(), comment_data).await?;
+		let timer_data = TimerForStart {
+			task_id: TaskId::from_store(task_id.clone()),
+			note: None,
+		};
+		TimeEntryBmc::start_timer(ctx.clone(), timer_data).await?;
+		events.take("Model");
+
+		// --- EXEC
+		ProjectBmc::delete(ctx.clone(), &id).await?;
+
+		// --- CHECK
+		let evts = events.take("Model");
+		let labels: Vec<_> = evts
+			.iter()
+			.map(|evt| (evt.topic.as_str(), evt.label.as_deref()))
+			.collect();
+		assert_eq!(
+			labels,
+			vec![("task", Some("delete_many")), ("project", Some("delete"))]
+		);
+		let tasks = TaskBmc::list(ctx.clone(), None).await?;
+		assert_eq!(tasks.len(), 1);
+		assert_eq!(tasks[0].title, "Task 02");
+		assert!(CommentBmc::list(ctx.clone(), None).await?.is_empty());
+		assert!(TimeEntryBmc::list(ctx.clone(), None).await?.is_empty());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_project_bmc_archived_read_only() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, events) = new_test_ctx().await?;
+		let id = ProjectBmc::create(ctx.clone(), project_for_create("Project 01"))
+			.await?
+			.id;
+		let project_id = ProjectId::from_store(id.clone());
+		let task_id = TaskBmc::create(ctx.clone(), task_for_create(&project_id, "Task 01"))
+			.await?
+			.id;
+		let comment_data = |body: &str| CommentForCreate {
+			task_id: TaskId::from_store(task_id.clone()),
+			body: body.to_string(),
+		};
+		let comment_id = CommentBmc::create(ctx.clone(), comment_data("Comment 01"))
+			.await?
+			.id;
+		ProjectBmc::archive(ctx.clone(), &id).await?;
+		events.take("Model");
+
+		// --- EXEC
+		let comment_create_res = CommentBmc::create(ctx.clone(), comment_data("Comment 02")).await;
+		let comment_delete_res = CommentBmc::delete(ctx.clone(), &comment_id).await;
+		let timer_data = TimerForStart {
+			task_id: TaskId::from_store(task_id.clone()),
+			note: None,
+		};
+		let timer_res = TimeEntryBmc::start_timer(ctx.clone(), timer_data).await;
+		let project_delete_res = ProjectBmc::delete(ctx.clone(), &id).await;
+
+		// --- CHECK
+		assert!(matches!(comment_create_res, Err(Error::ProjectArchived(_))));
+		assert!(matches!(comment_delete_res, Err(Error::ProjectArchived(_))));
+		assert!(matches!(timer_res, Err(Error::ProjectArchived(_))));
+		assert!(matches!(project_delete_res, Err(Error::ProjectArchived(_))));
+		assert!(events.take("Model").is_empty(), "no event when failing");
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -0,0 +1,357 @@
+//! Recurrence rule for recurring tasks.
+//!
+//! A rule is stored on the task as a small subset of the iCalendar RRULE syntax, e.g.,
+//! `FREQ=DAILY`, `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE`, `FREQ=MONTHLY;BYMONTHDAY=31`.
+//!
+//! Supported parts:
+//!   - `FREQ` - `DAILY`, `WEEKLY`, `MONTHLY`, `YEARLY` (required)
+//!   - `INTERVAL` - positive integer (default 1)
+//!   - `BYDAY` - comma separated `MO`..`SU` (`WEEKLY` only)
+//!   - `BYMONTHDAY` - 1..=31 (`MONTHLY` and `YEARLY` only)
+//!   - `UNTIL` - `YYYY-MM-DD` or `YYYYMMDD`, last date (inclusive) an occurrence can fall on
+//!
+//! Notes:
+//!   - When the target month is shorter than the anchor day (e.g., the 31st, or February 29 on a non leap year),
+//!     the occurrence is clamped to the last day of that month.
+//!   - Without `BYMONTHDAY`, the anchor day is the day of the date being shifted, so `Jan 31 -> Feb 28 -> Mar 28`.
+//!     Use `BYMONTHDAY=31` to stay on the month end.
+
+use crate::prelude::*;
+use crate::{Error, Result};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecurFreq {
+	Daily,
+	Weekly,
+	Monthly,
+	Yearly,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurRule {
+	pub freq: RecurFreq,
+	pub interval: u32,
+	pub by_day: Vec<Weekday>,
+	pub by_month_day: Option<u32>,
+	pub until: Option<NaiveDate>,
+}
+
+impl RecurRule {
+	/// Returns the next occurrence date strictly after `from`,
+	/// or None if the rule ends (i.e., `UNTIL`) before it.
+	pub fn next_after(&self, from: NaiveDate) -> Option<NaiveDate> {
+		let interval = self.interval as i64;
+
+		let next = match self.freq {
+			RecurFreq::Daily => from.checked_add_signed(Duration::days(interval))?,
+			RecurFreq::Weekly => self.next_weekly(from)?,
+			RecurFreq::Monthly => self.next_monthly(from, self.interval)?,
+			RecurFreq::Yearly => self.next_monthly(from, self.interval.checked_mul(12)?)?,
+		};
+
+		match self.until {
+			Some(until) if next > until => None,
+			_ => Some(next),
+		}
+	}
+
+	fn next_weekly(&self, from: NaiveDate) -> Option<NaiveDate> {
+		let interval = self.interval as i64;
+		if self.by_day.is_empty() {
+			return from.checked_add_signed(Duration::weeks(interval));
+		}
+
+		// remaining days of the current week (weeks start on Monday)
+		let from_idx = from.weekday().num_days_from_monday();
+		let mut days: Vec<u32> = self
+			.by_day
+			.iter()
+			.map(|d| d.num_days_from_monday())
+			.collect();
+		days.sort_unstable();
+
+		if let Some(day) = days.iter().find(|d| **d > from_idx) {
+			return from.checked_add_signed(Duration::days((day - from_idx) as i64));
+		}
+
+		// otherwise, first day of the week `interval` weeks later
+		let week_start = from.checked_sub_signed(Duration::days(from_idx as i64))?;
+		week_start.checked_add_signed(Duration::weeks(interval) + Duration::days(days[0] as i64))
+	}
+
+	/// Note: None when the date would be out of range (e.g., very large `INTERVAL`).
+	fn next_monthly(&self, from: NaiveDate, months: u32) -> Option<NaiveDate> {
+		let anchor_day = self.by_month_day.unwrap_or_else(|| from.day());
+
+		let month0 = from.month0().checked_add(months)?;
+		let year = from.year().checked_add(i32::try_from(month0 / 12).ok()?)?;
+		let month = month0 % 12 + 1;
+
+		let day = anchor_day.min(days_in_month(year, month));
+		NaiveDate::from_ymd_opt(year, month, day)
+	}
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+	let (next_year, next_month) = if month == 12 {
+		(year + 1, 1)
+	} else {
+		(year, month + 1)
+	};
+	NaiveDate::from_ymd_opt(next_year, next_month, 1)
+		.and_then(|d| d.pred_opt())
+		.map(|d| d.day())
+		.unwrap_or(28)
+}
+
+/// Parse a `YYYY-MM-DD` date (also accept `YYYYMMDD` as used by RRULE).
+pub fn parse_date(val: &str) -> Result<NaiveDate> {
+	NaiveDate::parse_from_str(val, "%Y-%m-%d")
+		.or_else(|_| NaiveDate::parse_from_str(val, "%Y%m%d"))
+		.map_err(|_| Error::DateInvalid(val.to_string()))
+}
+
+/// Format a date the way it is stored on the task (`YYYY-MM-DD`).
+pub fn format_date(date: NaiveDate) -> String {
+	date.format("%Y-%m-%d").to_string()
+}
+
+impl FromStr for RecurRule {
+	type Err = Error;
+
+	fn from_str(rule: &str) -> Result<Self> {
+		let invalid = |msg: &str| Error::RecurRuleInvalid(f!("{msg} (rule: '{rule}')"));
+
+		let mut freq = None;
+		let mut interval = 1;
+		let mut by_day = Vec::new();
+		let mut by_month_day = None;
+		let mut until = None;
+
+		let rule_body = rule.trim().trim_start_matches("RRULE:");
+		for part in rule_body.split(';').filter(|p| !p.is_empty()) {
+			let (key, val) = part
+				.split_once('=')
+				.ok_or_else(|| invalid(&f!("part '{part}' is not KEY=VALUE")))?;
+
+			match key.to_uppercase().as_str() {
+				"FREQ" => {
+					freq = Some(match val.to_uppercase().as_str() {
+						"DAILY" => RecurFreq::Daily,
+						"WEEKLY" => RecurFreq::Weekly,
+						"MONTHLY" => RecurFreq::Monthly,
+						"YEARLY" => RecurFreq::Yearly,
+						_ => return Err(invalid(&f!("FREQ '{val}' not supported"))),
+					})
+				}
+				"INTERVAL" => {
+					interval = val
+						.parse::<u32>()
+						.ok()
+						.filter(|v| *v > 0)
+						.ok_or_else(|| invalid("INTERVAL must be a positive integer"))?;
+				}
+				"BYDAY" => {
+					by_day = val
+						.split(',')
+						.map(|d| {
+							parse_weekday(d).ok_or_else(|| invalid(&f!("BYDAY '{d}' invalid")))
+						})
+						.collect::<Result<_>>()?;
+				}
+				"BYMONTHDAY" => {
+					by_month_day = Some(
+						val.parse::<u32>()
+							.ok()
+							.filter(|v| (1..=31).contains(v))
+							.ok_or_else(|| invalid("BYMONTHDAY must be between 1 and 31"))?,
+					);
+				}
+				"UNTIL" => {
+					// Note: only the date part of an RRULE UNTIL (e.g., 20240101T000000Z) is used.
+					let date = val.split('T').next().unwrap_or(val);
+					until = Some(parse_date(date).map_err(|_| invalid("UNTIL must be a date"))?);
+				}
+				_ => return Err(invalid(&f!("'{key}' not supported"))),
+			}
+		}
+
+		let freq = freq.ok_or_else(|| invalid("FREQ is required"))?;
+
+		if !by_day.is_empty() && freq != RecurFreq::Weekly {
+			return Err(invalid("BYDAY is only supported with FREQ=WEEKLY"));
+		}
+		if by_month_day.is_some() && !matches!(freq, RecurFreq::Monthly | RecurFreq::Yearly) {
+			return Err(invalid(
+				"BYMONTHDAY is only supported with FREQ=MONTHLY or FREQ=YEARLY",
+			));
+		}
+
+		Ok(RecurRule {
+			freq,
+			interval,
+			by_day,
+			by_month_day,
+			until,
+		})
+	}
+}
+
+fn parse_weekday(val: &str) -> Option<Weekday> {
+	Some(match val.trim().to_uppercase().as_str() {
+		"MO" => Weekday::Mon,
+		"TU" => Weekday::Tue,
+		"WE" => Weekday::Wed,
+		"TH" => Weekday::Thu,
+		"FR" => Weekday::Fri,
+		"SA" => Weekday::Sat,
+		"SU" => Weekday::Sun,
+		_ => return None,
+	})
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::{parse_date, RecurRule};
+
+	fn next(rule: &str, from: &str) -> anyhow::Result<Option<String>> {
+		let rule: RecurRule = rule.parse()?;
+		Ok(rule.next_after(parse_date(from)?).map(super::format_date))
+	}
+
+	#[test]
+	fn test_recur_daily_weekly() -> anyhow::Result<()> {
+		assert_eq!(
+			next("FREQ=DAILY", "2023-12-31")?.as_deref(),
+			Some("2024-01-01")
+		);
+		assert_eq!(
+			next("FREQ=DAILY;INTERVAL=3", "2024-02-27")?.as_deref(),
+			Some("2024-03-01")
+		);
+		assert_eq!(
+			next("FREQ=WEEKLY", "2023-06-01")?.as_deref(),
+			Some("2023-06-08")
+		);
+
+		// 2023-06-01 is a Thursday
+		assert_eq!(
+			next("FREQ=WEEKLY;BYDAY=MO,FR", "2023-06-01")?.as_deref(),
+			Some("2023-06-02")
+		);
+		assert_eq!(
+			next("FREQ=WEEKLY;BYDAY=MO,TH", "2023-06-01")?.as_deref(),
+			Some("2023-06-05")
+		);
+		assert_eq!(
+			next("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH", "2023-06-01")?.as_deref(),
+			Some("2023-06-12")
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_recur_month_end() -> anyhow::Result<()> {
+		// clamp to the last day of shorter months
+		assert_eq!(
+			next("FREQ=MONTHLY", "2023-01-31")?.as_deref(),
+			Some("2023-02-28")
+		);
+		assert_eq!(
+			next("FREQ=MONTHLY", "2023-03-31")?.as_deref(),
+			Some("2023-04-30")
+		);
+		assert_eq!(
+			next("FREQ=MONTHLY", "2023-12-31")?.as_deref(),
+			Some("2024-01-31")
+		);
+		assert_eq!(
+			next("FREQ=MONTHLY;INTERVAL=13", "2023-01-31")?.as_deref(),
+			Some("2024-02-29")
+		);
+
+		// BYMONTHDAY keeps the anchor day after a clamped month
+		assert_eq!(
+			next("FREQ=MONTHLY;BYMONTHDAY=31", "2023-02-28")?.as_deref(),
+			Some("2023-03-31")
+		);
+		assert_eq!(
+			next("FREQ=MONTHLY;BYMONTHDAY=31", "2023-03-31")?.as_deref(),
+			Some("2023-04-30")
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_recur_leap_year() -> anyhow::Result<()> {
+		assert_eq!(
+			next("FREQ=MONTHLY", "2024-01-30")?.as_deref(),
+			Some("2024-02-29")
+		);
+		assert_eq!(
+			next("FREQ=YEARLY", "2024-02-29")?.as_deref(),
+			Some("2025-02-28")
+		);
+		assert_eq!(
+			next("FREQ=YEARLY;INTERVAL=4", "2024-02-29")?.as_deref(),
+			Some("2028-02-29")
+		);
+		assert_eq!(
+			next("FREQ=YEARLY;BYMONTHDAY=29", "2027-02-28")?.as_deref(),
+			Some("2028-02-29")
+		);
+		// 2100 is not a leap year
+		assert_eq!(
+			next("FREQ=YEARLY;INTERVAL=4", "2096-02-29")?.as_deref(),
+			Some("2100-02-28")
+		);
+		assert_eq!(
+			next("FREQ=DAILY", "2024-02-28")?.as_deref(),
+			Some("2024-02-29")
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_recur_until_and_invalid() -> anyhow::Result<()> {
+		assert_eq!(
+			next("FREQ=DAILY;UNTIL=20240102", "2024-01-01")?.as_deref(),
+			Some("2024-01-02")
+		);
+		assert_eq!(next("FREQ=DAILY;UNTIL=2024-01-02", "2024-01-02")?, None);
+
+		// out of range dates, no next occurrence (rather than overflow)
+		assert_eq!(next("FREQ=YEARLY;INTERVAL=400000000", "2024-01-01")?, None);
+		assert_eq!(
+			next("FREQ=MONTHLY;INTERVAL=4294967295", "2024-12-01")?,
+			None
+		);
+		assert_eq!(next("FREQ=DAILY;INTERVAL=4294967295", "2024-01-01")?, None);
+
+		for rule in [
+			"",
+			"INTERVAL=2",
+			"FREQ=HOURLY",
+			"FREQ=DAILY;INTERVAL=0",
+			"FREQ=DAILY;BYDAY=MO",
+			"FREQ=WEEKLY;BYDAY=XX",
+			"FREQ=WEEKLY;BYMONTHDAY=2",
+			"FREQ=MONTHLY;BYMONTHDAY=32",
+			"FREQ=DAILY;COUNT=3",
+		] {
+			assert!(
+				rule.parse::<RecurRule>().is_err(),
+				"rule '{rule}' should be invalid"
+			);
+		}
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
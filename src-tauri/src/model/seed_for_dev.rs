@@ -1,37 +1,58 @@
-use crate::model::{ProjectForCreate, TaskForCreate};
+use crate::model::{ProjectForCreate, ProjectId, TaskForCreate};
 use crate::Result;
+use modql::filter::FilterNode;
+use modql::ListOptions;
 use std::sync::Arc;
 
 use super::ModelStore;
 
-/// Only use while developing. Convenient when to seed the store on start of the application.
-pub async fn seed_store_for_dev(model_manager: Arc<ModelStore>) -> Result<()> {
-	let ps = ["A", "B"].into_iter().map(|k| {
+/// Only use while developing. Convenient when to seed the store on start of the application (see `config::SeedConfig`).
+///
+/// Note: The projects are `Project A`, `Project B`, ... (then numbered after `Z`), with `tasks_per_project` tasks each.
+pub async fn seed_store_for_dev(
+	model_manager: Arc<ModelStore>,
+	projects: usize,
+	tasks_per_project: usize,
+) -> Result<()> {
+	let ps = (0..projects).map(|i| {
+		let k = if i < 26 {
+			char::from(b'A' + i as u8).to_string()
+		} else {
+			(i + 1).to_string()
+		};
+		let name = format!("Project {k}");
 		(
 			k,
 			ProjectForCreate {
-				name: format!("Project {k}"),
+				name,
+				description: None,
+				color: None,
+				icon: None,
+				archived: None,
+				template: None,
 			},
 		)
 	});
 
 	for (k, project) in ps {
 		let project_id = model_manager
-			.store()
+			.active_store()
 			.exec_create::<ProjectForCreate>("project", project)
 			.await?;
 
-		for i in 1..=200 {
+		for i in 1..=tasks_per_project {
 			let done = i % 2 == 0;
 			let task = TaskForCreate {
-				project_id: project_id.clone(),
+				project_id: ProjectId::from_store(project_id.clone()),
 				title: format!("Task {k}.{i}"),
 				desc: None,
 				done: Some(done),
+				due_date: None,
+				recur_rule: None,
 			};
 
 			model_manager
-				.store()
+				.active_store()
 				.exec_create::<TaskForCreate>("task", task)
 				.await?;
 		}
@@ -39,3 +60,17 @@ pub async fn seed_store_for_dev(model_manager: Arc<ModelStore>) -> Result<()> {
 
 	Ok(())
 }
+
+/// If the active workspace has no projects (e.g., a new memory store), to seed it only once for a persistent store.
+pub async fn is_store_empty(model_manager: &ModelStore) -> Result<bool> {
+	let opts = ListOptions {
+		limit: Some(1),
+		offset: None,
+		order_bys: None,
+	};
+	let projects = model_manager
+		.active_store()
+		.exec_select("project", None::<FilterNode>, opts)
+		.await?;
+	Ok(projects.is_empty())
+}
@@ -0,0 +1,206 @@
+//! Content-addressed file store for the attachment blobs.
+//!
+//! Files are stored by the hex sha256 of their content, as `<dir>/<hash[0..2]>/<hash>`,
+//! so adding the same content twice only stores it once.
+//!
+//! Notes:
+//!   - The store does not know which blobs are used. The caller (i.e., AttachmentBmc) gives the set of referenced
+//!     hashes to `retain` to garbage collect the others.
+//!   - Functions are blocking (std::fs), call them with `tokio::task::spawn_blocking` from async code.
+
+use crate::{Error, Result};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Clone)]
+pub(in crate::model) struct BlobStore {
+	dir: PathBuf,
+}
+
+/// Info of a blob after it has been added to the store.
+pub(in crate::model) struct BlobInfo {
+	pub hash: String,
+	pub size: u64,
+}
+
+impl BlobStore {
+	pub(in crate::model) fn new(dir: PathBuf) -> Self {
+		BlobStore { dir }
+	}
+
+	pub(in crate::model) fn path_for(&self, hash: &str) -> PathBuf {
+		let prefix = hash.get(..2).unwrap_or(hash);
+		self.dir.join(prefix).join(hash)
+	}
+
+	/// Copy a file into the store (if its content is not already in it).
+	pub(in crate::model) fn put_file(&self, src: &Path) -> Result<BlobInfo> {
+		if !src.is_file() {
+			return Err(Error::AttachmentSourceNotFile(
+				src.to_string_lossy().to_string(),
+			));
+		}
+
+		fs::create_dir_all(&self.dir)?;
+
+		// Copy to a temp file while hashing, so the source is read only once.
+		let tmp_path = self
+			.dir
+			.join(format!(".tmp-{}-{}", std::process::id(), tmp_suffix()));
+		let copy_res = copy_hashing(src, &tmp_path);
+		let (hash, size) = match copy_res {
+			Ok(res) => res,
+			Err(ex) => {
+				let _ = fs::remove_file(&tmp_path);
+				return Err(ex);
+			}
+		};
+
+		let blob_path = self.path_for(&hash);
+		if blob_path.exists() {
+			fs::remove_file(&tmp_path)?;
+		} else {
+			if let Some(parent) = blob_path.parent() {
+				fs::create_dir_all(parent)?;
+			}
+			fs::rename(&tmp_path, &blob_path)?;
+		}
+
+		Ok(BlobInfo { hash, size })
+	}
+
+	/// Delete all of the blobs which are not in `referenced`.
+	/// Returns the hashes of the deleted blobs.
+	pub(in crate::model) fn retain(&self, referenced: &HashSet<String>) -> Result<Vec<String>> {
+		let mut deleted = Vec::new();
+
+		let prefix_dirs = match fs::read_dir(&self.dir) {
+			Ok(dirs) => dirs,
+			Err(ex) if ex.kind() == io::ErrorKind::NotFound => return Ok(deleted),
+			Err(ex) => return Err(ex.into()),
+		};
+
+		for prefix_dir in prefix_dirs {
+			let prefix_dir = prefix_dir?.path();
+			if !prefix_dir.is_dir() {
+				continue;
+			}
+			for blob in fs::read_dir(&prefix_dir)? {
+				let blob_path = blob?.path();
+				let hash = blob_path
+					.file_name()
+					.map(|n| n.to_string_lossy().to_string())
+					.unwrap_or_default();
+				if !referenced.contains(&hash) {
+					fs::remove_file(&blob_path)?;
+					deleted.push(hash);
+				}
+			}
+			// remove the prefix dir if now empty (fails silently otherwise)
+			let _ = fs::remove_dir(&prefix_dir);
+		}
+
+		Ok(deleted)
+	}
+}
+
+fn copy_hashing(src: &Path, dst: &Path) -> Result<(String, u64)> {
+	let mut reader = File::open(src)?;
+	let mut writer = File::create(dst)?;
+	let mut hasher = Sha256::new();
+	let mut size = 0;
+
+	let mut buf = [0u8; 64 * 1024];
+	loop {
+		let n = reader.read(&mut buf)?;
+		if n == 0 {
+			break;
+		}
+		hasher.update(&buf[..n]);
+		writer.write_all(&buf[..n])?;
+		size += n as u64;
+	}
+	writer.sync_all()?;
+
+	Ok((format!("{:x}", hasher.finalize()), size))
+}
+
+fn tmp_suffix() -> u128 {
+	std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.map(|d| d.as_nanos())
+		.unwrap_or_default()
+}
+
+/// Best effort mime type, from the file content, then from the file extension.
+pub(in crate::model) fn guess_mime(path: &Path) -> String {
+	if let Ok(Some(kind)) = infer::get_from_path(path) {
+		return kind.mime_type().to_string();
+	}
+
+	let ext = path
+		.extension()
+		.map(|e| e.to_string_lossy().to_lowercase())
+		.unwrap_or_default();
+	match ext.as_str() {
+		"txt" | "log" => "text/plain",
+		"md" => "text/markdown",
+		"csv" => "text/csv",
+		"html" | "htm" => "text/html",
+		"json" => "application/json",
+		"svg" => "image/svg+xml",
+		_ => "application/octet-stream",
+	}
+	.to_string()
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::{guess_mime, BlobStore};
+	use std::collections::HashSet;
+	use std::fs;
+
+	#[test]
+	fn test_blob_store_put_dedup_retain() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let tmp_dir = tempfile::tempdir()?;
+		let store = BlobStore::new(tmp_dir.path().join("blobs"));
+		let file_a = tmp_dir.path().join("a.txt");
+		let file_b = tmp_dir.path().join("b.txt");
+		let file_c = tmp_dir.path().join("c.md");
+		fs::write(&file_a, "hello")?;
+		fs::write(&file_b, "hello")?;
+		fs::write(&file_c, "world")?;
+
+		// --- EXEC
+		let info_a = store.put_file(&file_a)?;
+		let info_b = store.put_file(&file_b)?;
+		let info_c = store.put_file(&file_c)?;
+
+		// --- CHECK
+		assert_eq!(
+			info_a.hash,
+			"2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+		);
+		assert_eq!(info_a.size, 5);
+		assert_eq!(info_a.hash, info_b.hash, "same content, same hash");
+		assert_eq!(fs::read_to_string(store.path_for(&info_a.hash))?, "hello");
+		assert_eq!(guess_mime(&file_c), "text/markdown");
+
+		// --- EXEC & CHECK - retain
+		let deleted = store.retain(&HashSet::from([info_c.hash.clone()]))?;
+		assert_eq!(deleted, vec![info_a.hash.clone()]);
+		assert!(!store.path_for(&info_a.hash).exists());
+		assert!(store.path_for(&info_c.hash).exists());
+
+		// --- CHECK - not a file
+		assert!(store.put_file(tmp_dir.path()).is_err());
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -1,15 +1,41 @@
+use crate::prelude::*;
+use crate::Result;
 use modql::filter::IntoFilterNodes;
-use surrealdb::sql::Value;
+use serde::Serialize;
+use surrealdb::sql::{Object, Value};
 
+mod blob_store;
 mod surreal_modql;
+mod surreal_serde;
 mod surreal_store;
 mod try_froms;
-mod x_take_impl;
 
 // --- Re-export
-pub(super) use surreal_store::SurrealStore;
+pub(super) use blob_store::{guess_mime, BlobStore};
+pub(super) use surreal_serde::from_object;
+use surreal_serde::to_value;
+pub use surreal_serde::Nanos;
+pub(super) use surreal_store::{new_tid, SurrealStore, DEFAULT_DB};
 
 // --- Marker traits for types that can be used for query.
-pub trait Creatable: Into<Value> {}
-pub trait Patchable: Into<Value> {}
+
+/// The data of a new record, serialized with `to_value` (i.e., the `None` fields are not stored).
+pub trait Creatable: Serialize {
+	/// The values of the properties not given (i.e., `None` or absent), e.g. `done: false`.
+	fn defaults() -> Vec<(&'static str, Value)> {
+		Vec::new()
+	}
+
+	/// The record content, with the defaults (e.g., for `SurrealStore::exec_create_all`).
+	fn to_object(&self) -> Result<Object> {
+		let mut obj: Object = W(to_value(self)?).try_into()?;
+		for (prop, default) in Self::defaults() {
+			obj.entry(prop.to_string()).or_insert(default);
+		}
+		Ok(obj)
+	}
+}
+
+/// The changes merged into a record, serialized with `to_value` (i.e., the `None` fields are not changed).
+pub trait Patchable: Serialize {}
 pub trait Filterable: IntoFilterNodes {}
@@ -98,6 +98,9 @@ fn sqlize(opval: OpVal, prop_name: &str, var_idx: &str) -> Result<(String, Value
 		OpVal::Int64(OpValInt64::Not(v)) => (f!("{prop_name} != ${var_idx}"), v.into()),
 		OpVal::Float64(OpValFloat64::Not(v)) => (f!("{prop_name} != ${var_idx}"), v.into()),
 		OpVal::Bool(OpValBool::Not(v)) => (f!("{prop_name} != ${var_idx}"), v.into()),
+		// In
+		OpVal::String(OpValString::In(v)) => (f!("{prop_name} INSIDE ${var_idx}"), v.into()),
+		OpVal::String(OpValString::NotIn(v)) => (f!("{prop_name} NOTINSIDE ${var_idx}"), v.into()),
 		// <
 		OpVal::String(OpValString::Lt(v)) => (f!("{prop_name} < ${var_idx}"), v.into()),
 		OpVal::Int64(OpValInt64::Lt(v)) => (f!("{prop_name} < ${var_idx}"), v.into()),
@@ -0,0 +1,738 @@
+//! Serde conversion between the surrealdb `Value`/`Object` and any serde type (e.g., the model entities).
+//!
+//! From Surreal, the conversion goes through `serde_json::Value`, with the Surreal specific values mapped as:
+//!   - `Thing` to its string form (e.g., `task:abc`), which is how the ids are exposed.
+//!   - `Datetime` to its RFC 3339 string.
+//!   - `None`/`Null` to `null` (i.e., `None` for `Option` fields).
+//!
+//! To Surreal, `to_value` serializes directly to a `Value` (see `ValueSerializer`), for the store data
+//! (e.g., `TaskForCreate`), where:
+//!   - The `None` struct fields are omitted (i.e., not stored, or not changed by a merge).
+//!   - The non finite floats (NaN, inf) and the integers over `i64` are errors (rather than stored as something else).
+//!
+//! Notes:
+//!   - The timestamps (e.g., `ctime`) are stored as epoch nanoseconds (i64), use `Nanos` for those fields.
+//!
+
+use crate::prelude::*;
+use crate::{Error, Result};
+use serde::de::{self, DeserializeOwned};
+use serde::ser::{self, Impossible};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Number as JsonNumber, Value as JsonValue};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use surrealdb::sql::{Number, Object, Value};
+use ts_rs::{Dependency, TS};
+
+/// Deserialize a serde type from a surrealdb Value.
+pub fn from_value<T: DeserializeOwned>(val: Value) -> Result<T> {
+	let json = json_from_value(val)?;
+	serde_json::from_value(json).map_err(|ex| Error::XValueSerde(ex.to_string()))
+}
+
+/// Deserialize a serde type from a surrealdb Object (e.g., a record from `SurrealStore::exec_get`).
+pub fn from_object<T: DeserializeOwned>(obj: Object) -> Result<T> {
+	from_value(Value::Object(obj))
+}
+
+/// Serialize a serde type to a surrealdb Value (e.g., the `Creatable` data for `SurrealStore::exec_create`).
+pub fn to_value<T: Serialize + ?Sized>(val: &T) -> Result<Value> {
+	val.serialize(ValueSerializer)
+		.map_err(|ex| Error::XValueSerde(ex.0))
+}
+
+// region:    --- Nanos
+
+/// Timestamp as epoch nanoseconds (e.g., `ctime`), stored as i64 and serialized as string
+/// (i.e., `string` in TypeScript, as JSON numbers are not i64 safe).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Nanos(pub i64);
+
+impl Serialize for Nanos {
+	fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+		serializer.collect_str(&self.0)
+	}
+}
+
+impl<'de> Deserialize<'de> for Nanos {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+		#[derive(Deserialize)]
+		#[serde(untagged)]
+		enum Repr {
+			Int(i64),
+			Str(String),
+		}
+		match Repr::deserialize(deserializer)? {
+			Repr::Int(v) => Ok(Nanos(v)),
+			Repr::Str(v) => v.parse().map(Nanos).map_err(de::Error::custom),
+		}
+	}
+}
+
+impl TS for Nanos {
+	fn name() -> String {
+		"string".to_string()
+	}
+	fn inline() -> String {
+		"string".to_string()
+	}
+	fn dependencies() -> Vec<Dependency> {
+		vec![]
+	}
+	fn transparent() -> bool {
+		false
+	}
+}
+
+// endregion: --- Nanos
+
+// region:    --- Value Serializer
+
+/// The error of the `ValueSerializer` (mapped to `Error::XValueSerde` by `to_value`).
+#[derive(Debug)]
+pub struct ValueSerError(String);
+
+impl Display for ValueSerError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		f.write_str(&self.0)
+	}
+}
+
+impl std::error::Error for ValueSerError {}
+
+impl ser::Error for ValueSerError {
+	fn custom<T: Display>(msg: T) -> Self {
+		ValueSerError(msg.to_string())
+	}
+}
+
+type SerResult<T> = core::result::Result<T, ValueSerError>;
+
+/// Serde Serializer to a surrealdb `Value` (see `to_value`).
+///
+/// Notes:
+///   - The enum variants are serialized as serde_json does (i.e., the unit variants as their name,
+///     the others as `{variant: value}`).
+///   - The map keys must serialize to strings.
+pub struct ValueSerializer;
+
+impl Serializer for ValueSerializer {
+	type Ok = Value;
+	type Error = ValueSerError;
+
+	type SerializeSeq = SerializeArray;
+	type SerializeTuple = SerializeArray;
+	type SerializeTupleStruct = SerializeArray;
+	type SerializeTupleVariant = SerializeVariant<SerializeArray>;
+	type SerializeMap = SerializeObject;
+	type SerializeStruct = SerializeObject;
+	type SerializeStructVariant = SerializeVariant<SerializeObject>;
+
+	fn serialize_bool(self, v: bool) -> SerResult<Value> {
+		Ok(v.into())
+	}
+
+	fn serialize_i8(self, v: i8) -> SerResult<Value> {
+		self.serialize_i64(v.into())
+	}
+
+	fn serialize_i16(self, v: i16) -> SerResult<Value> {
+		self.serialize_i64(v.into())
+	}
+
+	fn serialize_i32(self, v: i32) -> SerResult<Value> {
+		self.serialize_i64(v.into())
+	}
+
+	fn serialize_i64(self, v: i64) -> SerResult<Value> {
+		Ok(v.into())
+	}
+
+	fn serialize_u8(self, v: u8) -> SerResult<Value> {
+		self.serialize_i64(v.into())
+	}
+
+	fn serialize_u16(self, v: u16) -> SerResult<Value> {
+		self.serialize_i64(v.into())
+	}
+
+	fn serialize_u32(self, v: u32) -> SerResult<Value> {
+		self.serialize_i64(v.into())
+	}
+
+	fn serialize_u64(self, v: u64) -> SerResult<Value> {
+		match i64::try_from(v) {
+			Ok(v) => self.serialize_i64(v),
+			Err(_) => Err(ValueSerError(f!("number out of range: {v}"))),
+		}
+	}
+
+	fn serialize_f32(self, v: f32) -> SerResult<Value> {
+		self.serialize_f64(v.into())
+	}
+
+	fn serialize_f64(self, v: f64) -> SerResult<Value> {
+		if v.is_finite() {
+			Ok(v.into())
+		} else {
+			Err(ValueSerError(f!("number not finite: {v}")))
+		}
+	}
+
+	fn serialize_char(self, v: char) -> SerResult<Value> {
+		Ok(v.to_string().into())
+	}
+
+	fn serialize_str(self, v: &str) -> SerResult<Value> {
+		Ok(v.into())
+	}
+
+	fn serialize_bytes(self, v: &[u8]) -> SerResult<Value> {
+		Ok(Value::Array(
+			v.iter().map(|b| Value::from(*b)).collect::<Vec<_>>().into(),
+		))
+	}
+
+	fn serialize_none(self) -> SerResult<Value> {
+		Ok(Value::None)
+	}
+
+	fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> SerResult<Value> {
+		value.serialize(self)
+	}
+
+	fn serialize_unit(self) -> SerResult<Value> {
+		Ok(Value::Null)
+	}
+
+	fn serialize_unit_struct(self, _name: &'static str) -> SerResult<Value> {
+		self.serialize_unit()
+	}
+
+	fn serialize_unit_variant(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+	) -> SerResult<Value> {
+		self.serialize_str(variant)
+	}
+
+	fn serialize_newtype_struct<T: Serialize + ?Sized>(
+		self,
+		_name: &'static str,
+		value: &T,
+	) -> SerResult<Value> {
+		value.serialize(self)
+	}
+
+	fn serialize_newtype_variant<T: Serialize + ?Sized>(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+		value: &T,
+	) -> SerResult<Value> {
+		let mut obj = BTreeMap::new();
+		obj.insert(variant.to_string(), value.serialize(self)?);
+		Ok(Value::Object(obj.into()))
+	}
+
+	fn serialize_seq(self, len: Option<usize>) -> SerResult<SerializeArray> {
+		Ok(SerializeArray(Vec::with_capacity(len.unwrap_or(0))))
+	}
+
+	fn serialize_tuple(self, len: usize) -> SerResult<SerializeArray> {
+		self.serialize_seq(Some(len))
+	}
+
+	fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> SerResult<SerializeArray> {
+		self.serialize_seq(Some(len))
+	}
+
+	fn serialize_tuple_variant(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+		len: usize,
+	) -> SerResult<SerializeVariant<SerializeArray>> {
+		Ok(SerializeVariant {
+			variant,
+			inner: self.serialize_seq(Some(len))?,
+		})
+	}
+
+	fn serialize_map(self, _len: Option<usize>) -> SerResult<SerializeObject> {
+		Ok(SerializeObject::default())
+	}
+
+	fn serialize_struct(self, _name: &'static str, _len: usize) -> SerResult<SerializeObject> {
+		Ok(SerializeObject::default())
+	}
+
+	fn serialize_struct_variant(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+		_len: usize,
+	) -> SerResult<SerializeVariant<SerializeObject>> {
+		Ok(SerializeVariant {
+			variant,
+			inner: SerializeObject::default(),
+		})
+	}
+}
+
+/// The seqs and tuples, as `Value::Array`.
+pub struct SerializeArray(Vec<Value>);
+
+impl SerializeArray {
+	fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> SerResult<()> {
+		self.0.push(value.serialize(ValueSerializer)?);
+		Ok(())
+	}
+}
+
+impl ser::SerializeSeq for SerializeArray {
+	type Ok = Value;
+	type Error = ValueSerError;
+
+	fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> SerResult<()> {
+		self.push(value)
+	}
+
+	fn end(self) -> SerResult<Value> {
+		Ok(Value::Array(self.0.into()))
+	}
+}
+
+impl ser::SerializeTuple for SerializeArray {
+	type Ok = Value;
+	type Error = ValueSerError;
+
+	fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> SerResult<()> {
+		self.push(value)
+	}
+
+	fn end(self) -> SerResult<Value> {
+		ser::SerializeSeq::end(self)
+	}
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+	type Ok = Value;
+	type Error = ValueSerError;
+
+	fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> SerResult<()> {
+		self.push(value)
+	}
+
+	fn end(self) -> SerResult<Value> {
+		ser::SerializeSeq::end(self)
+	}
+}
+
+/// The maps and structs, as `Value::Object`, without the `None` struct fields.
+#[derive(Default)]
+pub struct SerializeObject {
+	obj: BTreeMap<String, Value>,
+	next_key: Option<String>,
+}
+
+impl ser::SerializeMap for SerializeObject {
+	type Ok = Value;
+	type Error = ValueSerError;
+
+	fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> SerResult<()> {
+		self.next_key = Some(key.serialize(MapKeySerializer)?);
+		Ok(())
+	}
+
+	fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> SerResult<()> {
+		let key = self
+			.next_key
+			.take()
+			.ok_or_else(|| ValueSerError("map value without key".to_string()))?;
+		self.obj.insert(key, value.serialize(ValueSerializer)?);
+		Ok(())
+	}
+
+	fn end(self) -> SerResult<Value> {
+		Ok(Value::Object(self.obj.into()))
+	}
+}
+
+impl ser::SerializeStruct for SerializeObject {
+	type Ok = Value;
+	type Error = ValueSerError;
+
+	fn serialize_field<T: Serialize + ?Sized>(
+		&mut self,
+		key: &'static str,
+		value: &T,
+	) -> SerResult<()> {
+		match value.serialize(ValueSerializer)? {
+			Value::None => {}
+			value => {
+				self.obj.insert(key.to_string(), value);
+			}
+		}
+		Ok(())
+	}
+
+	fn end(self) -> SerResult<Value> {
+		ser::SerializeMap::end(self)
+	}
+}
+
+/// The tuple and struct variants, as `{variant: value}`.
+pub struct SerializeVariant<S> {
+	variant: &'static str,
+	inner: S,
+}
+
+impl<S> SerializeVariant<S> {
+	fn wrap(variant: &'static str, value: Value) -> Value {
+		let mut obj = BTreeMap::new();
+		obj.insert(variant.to_string(), value);
+		Value::Object(obj.into())
+	}
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeArray> {
+	type Ok = Value;
+	type Error = ValueSerError;
+
+	fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> SerResult<()> {
+		self.inner.push(value)
+	}
+
+	fn end(self) -> SerResult<Value> {
+		let value = ser::SerializeSeq::end(self.inner)?;
+		Ok(Self::wrap(self.variant, value))
+	}
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeObject> {
+	type Ok = Value;
+	type Error = ValueSerError;
+
+	fn serialize_field<T: Serialize + ?Sized>(
+		&mut self,
+		key: &'static str,
+		value: &T,
+	) -> SerResult<()> {
+		ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+	}
+
+	fn end(self) -> SerResult<Value> {
+		let value = ser::SerializeMap::end(self.inner)?;
+		Ok(Self::wrap(self.variant, value))
+	}
+}
+
+/// The map keys, which must be strings (or unit variants).
+struct MapKeySerializer;
+
+fn key_must_be_string() -> ValueSerError {
+	ValueSerError("map key must be a string".to_string())
+}
+
+impl Serializer for MapKeySerializer {
+	type Ok = String;
+	type Error = ValueSerError;
+
+	type SerializeSeq = Impossible<String, ValueSerError>;
+	type SerializeTuple = Impossible<String, ValueSerError>;
+	type SerializeTupleStruct = Impossible<String, ValueSerError>;
+	type SerializeTupleVariant = Impossible<String, ValueSerError>;
+	type SerializeMap = Impossible<String, ValueSerError>;
+	type SerializeStruct = Impossible<String, ValueSerError>;
+	type SerializeStructVariant = Impossible<String, ValueSerError>;
+
+	fn serialize_str(self, v: &str) -> SerResult<String> {
+		Ok(v.to_string())
+	}
+
+	fn serialize_char(self, v: char) -> SerResult<String> {
+		Ok(v.to_string())
+	}
+
+	fn serialize_unit_variant(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+	) -> SerResult<String> {
+		Ok(variant.to_string())
+	}
+
+	fn serialize_newtype_struct<T: Serialize + ?Sized>(
+		self,
+		_name: &'static str,
+		value: &T,
+	) -> SerResult<String> {
+		value.serialize(self)
+	}
+
+	fn serialize_bool(self, _v: bool) -> SerResult<String> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_i8(self, _v: i8) -> SerResult<String> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_i16(self, _v: i16) -> SerResult<String> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_i32(self, _v: i32) -> SerResult<String> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_i64(self, _v: i64) -> SerResult<String> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_u8(self, _v: u8) -> SerResult<String> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_u16(self, _v: u16) -> SerResult<String> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_u32(self, _v: u32) -> SerResult<String> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_u64(self, _v: u64) -> SerResult<String> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_f32(self, _v: f32) -> SerResult<String> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_f64(self, _v: f64) -> SerResult<String> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_bytes(self, _v: &[u8]) -> SerResult<String> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_none(self) -> SerResult<String> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> SerResult<String> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_unit(self) -> SerResult<String> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_unit_struct(self, _name: &'static str) -> SerResult<String> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_newtype_variant<T: Serialize + ?Sized>(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		_variant: &'static str,
+		_value: &T,
+	) -> SerResult<String> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_seq(self, _len: Option<usize>) -> SerResult<Self::SerializeSeq> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_tuple(self, _len: usize) -> SerResult<Self::SerializeTuple> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_tuple_struct(
+		self,
+		_name: &'static str,
+		_len: usize,
+	) -> SerResult<Self::SerializeTupleStruct> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_tuple_variant(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		_variant: &'static str,
+		_len: usize,
+	) -> SerResult<Self::SerializeTupleVariant> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_map(self, _len: Option<usize>) -> SerResult<Self::SerializeMap> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_struct(
+		self,
+		_name: &'static str,
+		_len: usize,
+	) -> SerResult<Self::SerializeStruct> {
+		Err(key_must_be_string())
+	}
+
+	fn serialize_struct_variant(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		_variant: &'static str,
+		_len: usize,
+	) -> SerResult<Self::SerializeStructVariant> {
+		Err(key_must_be_string())
+	}
+}
+
+// endregion: --- Value Serializer
+
+// region:    --- Json Conversions
+
+fn json_from_value(val: Value) -> Result<JsonValue> {
+	let json = match val {
+		Value::None | Value::Null => JsonValue::Null,
+		Value::False => JsonValue::Bool(false),
+		Value::True => JsonValue::Bool(true),
+		Value::Number(Number::Int(v)) => JsonValue::Number(v.into()),
+		Value::Number(num) => {
+			let v = num.as_float();
+			match JsonNumber::from_f64(v) {
+				Some(num) => JsonValue::Number(num),
+				None => return Err(Error::XValueSerde(f!("number not finite: {v}"))),
+			}
+		}
+		Value::Strand(strand) => JsonValue::String(strand.as_string()),
+		Value::Datetime(datetime) => JsonValue::String(datetime.0.to_rfc3339()),
+		Value::Thing(thing) => JsonValue::String(thing.to_string()),
+		Value::Array(array) => JsonValue::Array(
+			array
+				.into_iter()
+				.map(json_from_value)
+				.collect::<Result<_>>()?,
+		),
+		Value::Object(obj) => JsonValue::Object(
+			obj.0
+				.into_iter()
+				.map(|(k, v)| json_from_value(v).map(|v| (k, v)))
+				.collect::<Result<Map<_, _>>>()?,
+		),
+		other => return Err(Error::XValueSerde(f!("value not supported: {other}"))),
+	};
+
+	Ok(json)
+}
+
+// endregion: --- Json Conversions
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::utils::map;
+	use surrealdb::sql::{Datetime, Thing};
+
+	#[derive(Serialize, Deserialize, Debug, PartialEq)]
+	struct Item {
+		id: String,
+		ctime: Nanos,
+		mtime: Option<Nanos>,
+		done: bool,
+		score: f64,
+		due: Option<String>,
+		tags: Vec<String>,
+	}
+
+	#[test]
+	fn test_surreal_serde_from_object() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let due = Datetime::default();
+		let obj: Object = map![
+			"id".into() => Value::Thing(Thing::from(("item", "abc"))),
+			"ctime".into() => 1234_i64.into(),
+			"done".into() => true.into(),
+			"score".into() => 1.5_f64.into(),
+			"due".into() => Value::Datetime(due.clone()),
+			"tags".into() => vec!["a", "b"].into(),
+		]
+		.into();
+
+		// --- EXEC
+		let item: Item = from_object(obj)?;
+
+		// --- CHECK
+		assert_eq!(item.id, "item:abc");
+		assert_eq!(item.ctime, Nanos(1234));
+		assert_eq!(item.mtime, None);
+		assert!(item.done);
+		assert_eq!(item.score, 1.5);
+		assert_eq!(item.due, Some(due.0.to_rfc3339()));
+		assert_eq!(item.tags, vec!["a", "b"]);
+
+		Ok(())
+	}
+	#[test]
+	fn test_surreal_serde_to_value_skip_none() -> anyhow::Result<()> {
+		// --- FIXTURE
+		#[derive(Serialize)]
+		struct Data {
+			title: String,
+			done: bool,
+			size: u64,
+			desc: Option<String>,
+			tags: Vec<&'static str>,
+		}
+		let data = Data {
+			title: "Task A".to_string(),
+			done: false,
+			size: 12,
+			desc: None,
+			tags: vec!["a"],
+		};
+
+		// --- EXEC
+		let value = to_value(&data)?;
+
+		// --- CHECK
+		let obj: Object = W(value).try_into()?;
+		assert_eq!(obj.get("title"), Some(&Value::from("Task A")));
+		assert_eq!(obj.get("done"), Some(&Value::False));
+		assert_eq!(obj.get("size"), Some(&Value::from(12_i64)));
+		assert_eq!(obj.get("tags"), Some(&Value::from(vec!["a"])));
+		assert!(obj.get("desc").is_none(), "None should not be stored");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_surreal_serde_not_finite_err() -> anyhow::Result<()> {
+		// --- EXEC
+		let to_res = to_value(&map!["score" => f64::NAN]);
+		let too_big_res = to_value(&u64::MAX);
+		let from_res = from_object::<BTreeMap<String, Option<f64>>>(
+			map!["score".into() => f64::INFINITY.into()].into(),
+		);
+
+		// --- CHECK
+		assert!(matches!(to_res, Err(Error::XValueSerde(_))), "{to_res:?}");
+		assert!(matches!(too_big_res, Err(Error::XValueSerde(_))));
+		assert!(matches!(from_res, Err(Error::XValueSerde(_))));
+
+		Ok(())
+	}
+}
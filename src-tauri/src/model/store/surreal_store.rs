@@ -4,45 +4,127 @@
 //! to the rest of the application code (.e.g, Backend Model Controllers)
 
 use crate::model::store::surreal_modql::build_select_query;
+use crate::model::store::surreal_serde::{from_value, to_value};
 use crate::model::store::{Creatable, Patchable};
 use crate::prelude::*;
-use crate::utils::{map, XTake};
+use crate::utils::map;
 use crate::{Error, Result};
 use modql::filter::FilterGroups;
 use modql::ListOptions;
-use surrealdb::dbs::Session;
+use parking_lot::RwLock;
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use surrealdb::dbs::{Response, Session};
 use surrealdb::kvs::Datastore;
-use surrealdb::sql::{thing, Array, Datetime, Object, Value};
+use surrealdb::sql::{thing, Array, Datetime, Id, Object, Thing, Value};
+use tracing::{instrument, Span};
 
 // --- Store definition and implementation
 //     Note: This is used to normalize the store access for what is
 //           needed for this application.
 
+const NS: &str = "appns";
+pub(in crate::model) const DEFAULT_DB: &str = "appdb";
+
 /// Store struct normalizing CRUD SurrealDB application calls
+///
+/// Note: The session (i.e., the database) can be switched at runtime (see `set_db`),
+///       each call works on a copy of the session taken when it starts, and the operations of
+///       several calls work on a store of their database (see `on_db`, and `ModelStore::store`).
 pub(in crate::model) struct SurrealStore {
-	ds: Datastore,
-	ses: Session,
+	ds: Arc<Datastore>,
+	ses: RwLock<Session>,
 }
 
 impl SurrealStore {
-	pub(in crate::model) async fn new() -> Result<Self> {
-		let ds = Datastore::new("memory").await?;
-		let ses = Session::for_db("appns", "appdb");
-		Ok(SurrealStore { ds, ses })
+	/// New store on the datastore of the path (e.g., `memory`).
+	pub(in crate::model) async fn new(path: &str) -> Result<Self> {
+		let ds = Datastore::new(path).await?;
+		Ok(SurrealStore {
+			ds: Arc::new(ds),
+			ses: RwLock::new(Session::for_db(NS, DEFAULT_DB)),
+		})
+	}
+
+	/// New store on the same datastore, for another database.
+	pub(in crate::model) fn with_db(&self, db: &str) -> Result<Self> {
+		Ok(SurrealStore {
+			ds: self.ds.clone(),
+			ses: RwLock::new(Session::for_db(NS, ensure_db_name(db)?)),
+		})
+	}
+
+	/// New store on the same datastore, for a database of `db` (i.e., with a valid name).
+	/// Note: Not affected by a later `set_db` of this store.
+	pub(in crate::model) fn on_db(&self, db: &str) -> Self {
+		SurrealStore {
+			ds: self.ds.clone(),
+			ses: RwLock::new(Session::for_db(NS, db)),
+		}
+	}
+
+	/// Name of the current database.
+	pub(in crate::model) fn db(&self) -> String {
+		self.ses.read().db.clone().unwrap_or_default()
+	}
+
+	/// Switch the database of this store. Calls in progress complete on the previous one.
+	pub(in crate::model) fn set_db(&self, db: &str) -> Result<()> {
+		*self.ses.write() = Session::for_db(NS, ensure_db_name(db)?);
+		Ok(())
+	}
+
+	fn session(&self) -> Session {
+		self.ses.read().clone()
+	}
+
+	/// Execute the SurrealQL, recorded on the span of the caller (i.e., the `sql` field of the `exec_..` spans).
+	async fn execute(
+		&self,
+		sql: &str,
+		ses: &Session,
+		vars: Option<BTreeMap<String, Value>>,
+		strict: bool,
+	) -> Result<Vec<Response>> {
+		Span::current().record("sql", sql);
+		Ok(self.ds.execute(sql, ses, vars, strict).await?)
+	}
+
+	/// Remove a database (and all of its content) of the namespace.
+	#[instrument(level = "debug", skip_all, fields(db = db, sql))]
+	pub(in crate::model) async fn exec_remove_db(&self, db: &str) -> Result<()> {
+		// Note: database names cannot be query parameters, hence the name check.
+		let sql = f!("REMOVE DATABASE {}", ensure_db_name(db)?);
+
+		// Removing a database requires a namespace level session.
+		let ses = Session::for_ns(NS);
+		let ress = self.execute(&sql, &ses, None, false).await?;
+
+		let first_res = ress.into_iter().next().expect("Did not get a response");
+
+		first_res.result?;
+
+		Ok(())
 	}
 
-	pub(in crate::model) async fn exec_get(&self, tid: &str) -> Result<Object> {
+	#[instrument(level = "debug", skip_all, fields(tb = tb, id = tid, sql))]
+	pub(in crate::model) async fn exec_get(&self, tb: &str, tid: &str) -> Result<Object> {
 		let sql = "SELECT * FROM $th";
 
-		let vars = map!["th".into() => thing(tid)?.into()];
+		let vars = map!["th".into() => table_thing(tb, tid)?.into()];
 
-		let ress = self.ds.execute(sql, &self.ses, Some(vars), true).await?;
+		let ress = self.execute(sql, &self.session(), Some(vars), true).await?;
 
 		let first_res = ress.into_iter().next().expect("Did not get a response");
 
-		W(first_res.result?.first()).try_into()
+		match first_res.result?.first() {
+			Value::None => Err(Error::StoreRecordNotFound(tid.to_string())),
+			val => W(val).try_into(),
+		}
 	}
 
+	#[instrument(level = "debug", skip_all, fields(tb = tb, sql))]
 	pub(in crate::model) async fn exec_create<T: Creatable>(
 		&self,
 		tb: &str,
@@ -50,7 +132,7 @@ impl SurrealStore {
 	) -> Result<String> {
 		let sql = "CREATE type::table($tb) CONTENT $data RETURN id";
 
-		let mut data: Object = W(data.into()).try_into()?;
+		let mut data = data.to_object()?;
 		let now = Datetime::default().timestamp_nanos();
 		data.insert("ctime".into(), now.into());
 
@@ -58,7 +140,9 @@ impl SurrealStore {
 			"tb".into() => tb.into(),
 			"data".into() => Value::from(data)];
 
-		let ress = self.ds.execute(sql, &self.ses, Some(vars), false).await?;
+		let ress = self
+			.execute(sql, &self.session(), Some(vars), false)
+			.await?;
 		let first_val = ress
 			.into_iter()
 			.next()
@@ -66,7 +150,7 @@ impl SurrealStore {
 			.expect("id not returned")?;
 
 		if let Value::Object(mut val) = first_val.first() {
-			val.x_take_val::<String>("id")
+			take_prop::<String>(&mut val, "id")
 				.map_err(|ex| Error::StoreFailToCreate(f!("exec_create {tb} {ex}")))
 		} else {
 			Err(Error::StoreFailToCreate(f!(
@@ -75,25 +159,88 @@ impl SurrealStore {
 		}
 	}
 
+	/// Create all of the records (`(tid, data)`, see `new_tid`) in one transaction,
+	/// so either all or none of them are created. Returns the created ids.
+	///
+	/// Note: The `ctime`s follow the records order (i.e., sorting by ctime keeps it).
+	#[instrument(level = "debug", skip_all, fields(records = records.len(), sql))]
+	pub(in crate::model) async fn exec_create_all(
+		&self,
+		records: Vec<(String, Object)>,
+	) -> Result<Vec<String>> {
+		let now = Datetime::default().timestamp_nanos();
+
+		let mut sql = String::from("BEGIN TRANSACTION;\n");
+		let mut vars = BTreeMap::new();
+		for (idx, (tid, mut data)) in records.into_iter().enumerate() {
+			data.insert("ctime".into(), (now + idx as i64).into());
+			sql.push_str(&f!("CREATE $th{idx} CONTENT $data{idx} RETURN id;\n"));
+			vars.insert(f!("th{idx}"), thing(&tid)?.into());
+			vars.insert(f!("data{idx}"), Value::from(data));
+		}
+		sql.push_str("COMMIT TRANSACTION;");
+
+		let ress = self
+			.execute(&sql, &self.session(), Some(vars), false)
+			.await?;
+
+		let mut ids = Vec::new();
+		for res in ress {
+			// Note: on failure, all of the statements of the transaction return an error.
+			if let Value::Object(mut val) = res.result?.first() {
+				ids.push(take_prop(&mut val, "id")?);
+			}
+		}
+
+		Ok(ids)
+	}
+
+	/// Get the records of the ids (of the table), in one query. Ids without a record are skipped.
+	#[instrument(level = "debug", skip_all, fields(tb = tb, ids = tids.len(), sql))]
+	pub(in crate::model) async fn exec_get_many(
+		&self,
+		tb: &str,
+		tids: &[String],
+	) -> Result<Vec<Object>> {
+		let sql = "SELECT * FROM $ths";
+
+		let vars = map!["ths".into() => things(tb, tids)?];
+
+		let ress = self.execute(sql, &self.session(), Some(vars), true).await?;
+
+		let first_res = ress.into_iter().next().expect("Did not get a response");
+
+		let array: Array = W(first_res.result?).try_into()?;
+
+		// Note: missing records come back as NONE.
+		array
+			.into_iter()
+			.filter(|value| value.is_some())
+			.map(|value| W(value).try_into())
+			.collect()
+	}
+
+	#[instrument(level = "debug", skip_all, fields(tb = tb, id = tid, sql))]
 	pub(in crate::model) async fn exec_merge<T: Patchable>(
 		&self,
+		tb: &str,
 		tid: &str,
 		data: T,
 	) -> Result<String> {
 		let sql = "UPDATE $th MERGE $data RETURN id";
 
 		let vars = map![
-			"th".into() => thing(tid)?.into(),
-			"data".into() => data.into()];
+			"th".into() => table_thing(tb, tid)?.into(),
+			"data".into() => to_value(&data)?];
 
-		let ress = self.ds.execute(sql, &self.ses, Some(vars), true).await?;
+		let ress = self.execute(sql, &self.session(), Some(vars), true).await?;
 
 		let first_res = ress.into_iter().next().expect("id not returned");
 
 		let result = first_res.result?;
 
 		if let Value::Object(mut val) = result.first() {
-			val.x_take_val("id")
+			take_prop(&mut val, "id")
 		} else {
 			Err(Error::StoreFailToCreate(f!(
 				"exec_merge {tid}, nothing returned."
@@ -101,12 +248,61 @@ impl SurrealStore {
 		}
 	}
 
-	pub(in crate::model) async fn exec_delete(&self, tid: &str) -> Result<String> {
+	/// Merge the same data into all of the records of the ids, in one statement.
+	/// Returns the ids of the updated records.
+	///
+	/// Note: Ids must be of existing records (e.g., from `exec_get_many`), as UPDATE creates missing ones.
+	#[instrument(level = "debug", skip_all, fields(tb = tb, ids = tids.len(), sql))]
+	pub(in crate::model) async fn exec_merge_many<T: Patchable>(
+		&self,
+		tb: &str,
+		tids: &[String],
+		data: T,
+	) -> Result<Vec<String>> {
+		let sql = "UPDATE $ths MERGE $data RETURN id";
+
+		let vars = map![
+			"ths".into() => things(tb, tids)?,
+			"data".into() => to_value(&data)?];
+
+		let ress = self
+			.execute(sql, &self.session(), Some(vars), false)
+			.await?;
+
+		let first_res = ress.into_iter().next().expect("Did not get a response");
+
+		take_ids(first_res.result?)
+	}
+
+	/// Delete all of the records of the ids (of the table), in one statement. Returns the ids of the deleted records.
+	#[instrument(level = "debug", skip_all, fields(tb = tb, ids = tids.len(), sql))]
+	pub(in crate::model) async fn exec_delete_many(
+		&self,
+		tb: &str,
+		tids: &[String],
+	) -> Result<Vec<String>> {
+		let sql = "DELETE $ths RETURN BEFORE";
+
+		let vars = map!["ths".into() => things(tb, tids)?];
+
+		let ress = self
+			.execute(sql, &self.session(), Some(vars), false)
+			.await?;
+
+		let first_res = ress.into_iter().next().expect("Did not get a response");
+
+		take_ids(first_res.result?)
+	}
+
+	#[instrument(level = "debug", skip_all, fields(tb = tb, id = tid, sql))]
+	pub(in crate::model) async fn exec_delete(&self, tb: &str, tid: &str) -> Result<String> {
 		let sql = "DELETE $th";
 
-		let vars = map!["th".into() => thing(tid)?.into()];
+		let vars = map!["th".into() => table_thing(tb, tid)?.into()];
 
-		let ress = self.ds.execute(sql, &self.ses, Some(vars), false).await?;
+		let ress = self
+			.execute(sql, &self.session(), Some(vars), false)
+			.await?;
 
 		let first_res = ress.into_iter().next().expect("Did not get a response");
 
@@ -117,6 +313,7 @@ impl SurrealStore {
 		Ok(tid.to_string())
 	}
 
+	#[instrument(level = "debug", skip_all, fields(tb = tb, sql))]
 	pub(in crate::model) async fn exec_select<O: Into<FilterGroups>>(
 		&self,
 		tb: &str,
@@ -126,7 +323,9 @@ impl SurrealStore {
 		let filter_or_groups = filter_groups.map(|v| v.into());
 		let (sql, vars) = build_select_query(tb, filter_or_groups, list_options)?;
 
-		let ress = self.ds.execute(&sql, &self.ses, Some(vars), false).await?;
+		let ress = self
+			.execute(&sql, &self.session(), Some(vars), false)
+			.await?;
 
 		let first_res = ress.into_iter().next().expect("Did not get a response");
 
@@ -136,6 +335,96 @@ impl SurrealStore {
 		// build the list of objects
 		array.into_iter().map(|value| W(value).try_into()).collect()
 	}
+
+	/// Count the records of a table grouped by the value of `field`.
+	/// Note: `field` is interpolated in the query, so it must come from the code, not from the user.
+	#[instrument(level = "debug", skip_all, fields(tb = tb, sql))]
+	pub(in crate::model) async fn exec_count_group_by(
+		&self,
+		tb: &str,
+		field: &'static str,
+	) -> Result<BTreeMap<String, u32>> {
+		let sql = f!("SELECT {field}, count() AS count FROM type::table($tb) GROUP BY {field}");
+
+		let vars = map!["tb".into() => tb.into()];
+
+		let ress = self
+			.execute(&sql, &self.session(), Some(vars), false)
+			.await?;
+
+		let first_res = ress.into_iter().next().expect("Did not get a response");
+
+		let array: Array = W(first_res.result?).try_into()?;
+
+		array
+			.into_iter()
+			.map(|value| {
+				let mut obj: Object = W(value).try_into()?;
+				let key = take_prop::<String>(&mut obj, field)?;
+				let count = take_prop::<i64>(&mut obj, "count")?;
+				Ok((key, count as u32))
+			})
+			.collect()
+	}
+}
+
+/// The Thing of a record id, which must be of the table (e.g., not a `task:..` id for a project).
+/// Note: Also prevents `UPDATE` from creating a record in another table.
+fn table_thing(tb: &str, tid: &str) -> Result<Thing> {
+	let th = thing(tid)?;
+	if th.tb == tb {
+		Ok(th)
+	} else {
+		Err(Error::StoreIdNotOfTable(tid.to_string()))
+	}
+}
+
+/// Array value of the record ids (e.g., for `SELECT * FROM $ths`), which must all be of the table (see `table_thing`).
+fn things(tb: &str, tids: &[String]) -> Result<Value> {
+	let things = tids
+		.iter()
+		.map(|tid| table_thing(tb, tid).map(Value::from))
+		.collect::<Result<Vec<_>>>()?;
+	Ok(Value::Array(things.into()))
+}
+
+/// Ids of the records returned by a statement (e.g., `... RETURN id`).
+fn take_ids(result: Value) -> Result<Vec<String>> {
+	let array: Array = W(result).try_into()?;
+	array
+		.into_iter()
+		// Note: missing records (e.g., for `DELETE .. RETURN BEFORE`) come back as NONE.
+		.filter(|value| value.is_some())
+		.map(|value| {
+			let mut obj: Object = W(value).try_into()?;
+			take_prop(&mut obj, "id")
+		})
+		.collect()
+}
+
+/// Take the property `k` of the object, deserialized with `surreal_serde` (e.g., `Thing` as string).
+fn take_prop<T: DeserializeOwned>(obj: &mut Object, k: &str) -> Result<T> {
+	let val = obj
+		.remove(k)
+		.ok_or_else(|| Error::XPropertyNotFound(k.to_string()))?;
+	from_value(val)
+}
+
+/// New record id (e.g., `project:xz4u..`) for `exec_create_all`.
+pub(in crate::model) fn new_tid(tb: &str) -> String {
+	Thing::from((tb.to_string(), Id::rand())).to_string()
+}
+
+/// Only allow simple identifiers (i.e., ascii alphanumeric and `_`) as database names.
+fn ensure_db_name(db: &str) -> Result<&str> {
+	let valid = !db.is_empty()
+		&& db.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+		&& !db.starts_with(|c: char| c.is_ascii_digit());
+	if valid {
+		Ok(db)
+	} else {
+		Err(Error::StoreDbNameInvalid(db.to_string()))
+	}
 }
 
 // region:    --- Tests
@@ -145,8 +434,8 @@ mod tests {
 	use std::sync::Arc;
 	use tokio::sync::OnceCell;
 
+	use super::take_prop;
 	use crate::model::ModelStore;
-	use crate::utils::XTake;
 	use modql::ListOptions;
 
 	static STORE_ONCE: OnceCell<Arc<ModelStore>> = OnceCell::const_new();
@@ -157,10 +446,11 @@ mod tests {
 		STORE_ONCE
 			.get_or_init(|| async {
 				// create and seed the store
-				let model_manager = ModelStore::new().await.unwrap();
+				let app_data_dir = std::env::temp_dir().join("awesome-app-test");
+				let model_manager = ModelStore::new(&app_data_dir, "memory").await.unwrap();
 				let model_manager = Arc::new(model_manager);
 
-				crate::model::seed_store_for_dev(model_manager.clone())
+				crate::model::seed_store_for_dev(model_manager.clone(), 2, 200)
 					.await
 					.unwrap();
 				model_manager
@@ -220,14 +510,14 @@ mod tests {
 
 		// --- EXEC
 		let mut rs = model_manager
-			.store()
+			.active_store()
 			.exec_select("project", Some(filter), ListOptions::default())
 			.await?;
 
 		// --- CHECKS
 		assert_eq!(rs.len(), 1, "number of projects returned");
 		let mut obj = rs.pop().unwrap();
-		assert_eq!(obj.x_take::<String>("name")?.unwrap(), "Project A");
+		assert_eq!(take_prop::<String>(&mut obj, "name")?, "Project A");
 
 		Ok(())
 	}
@@ -240,10 +530,10 @@ mod tests {
 		// get the "Project A" project_id
 		let project_filter_node = FilterNode::from(("name", "Project A"));
 		let mut rs = model_manager
-			.store()
+			.active_store()
 			.exec_select("project", Some(project_filter_node), ListOptions::default())
 			.await?;
-		let project_id = rs.pop().unwrap().x_take_val::<String>("id")?;
+		let project_id: String = take_prop(&mut rs.pop().unwrap(), "id")?;
 
 		let filter = TaskFilter {
 			project_id: Some(OpValString::from(project_id).into()),
@@ -254,7 +544,7 @@ mod tests {
 
 		// --- EXEC
 		let rs = model_manager
-			.store()
+			.active_store()
 			.exec_select("task", Some(filter), ListOptions::default())
 			.await?;
 
@@ -276,7 +566,7 @@ mod tests {
 
 		// --- EXEC
 		let mut rs = model_manager
-			.store()
+			.active_store()
 			.exec_select(
 				"task",
 				Some(filter_node),
@@ -290,11 +580,11 @@ mod tests {
 		// --- CHECK
 		assert_eq!(
 			"Task B.200",
-			rs.pop().unwrap().x_take_val::<String>("title")?
+			take_prop::<String>(&mut rs.pop().unwrap(), "title")?
 		);
 		assert_eq!(
 			"Task A.200",
-			rs.pop().unwrap().x_take_val::<String>("title")?
+			take_prop::<String>(&mut rs.pop().unwrap(), "title")?
 		);
 
 		Ok(())
@@ -308,7 +598,7 @@ mod tests {
 
 		// --- EXEC
 		let rs = model_manager
-			.store()
+			.active_store()
 			.exec_select("task", Some(filter_node), ListOptions::default())
 			.await?;
 
@@ -326,7 +616,7 @@ mod tests {
 
 		// --- EXEC
 		let rs = model_manager
-			.store()
+			.active_store()
 			.exec_select("task", Some(filter_node), ListOptions::default())
 			.await?;
 
@@ -336,6 +626,225 @@ mod tests {
 		Ok(())
 	}
 
+	#[tokio::test]
+	async fn test_surreal_select_in() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let model_manager = get_shared_test_store().await;
+		let titles = vec![
+			"Task A.1".to_string(),
+			"Task B.2".to_string(),
+			"Task C.3".to_string(),
+		];
+		let filter_node = FilterNode::from(("title", OpValString::In(titles)));
+
+		// --- EXEC
+		let rs = model_manager
+			.active_store()
+			.exec_select("task", Some(filter_node), ListOptions::default())
+			.await?;
+
+		// --- CHECK
+		assert_eq!(rs.len(), 2, "Number of tasks with title in the list");
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_surreal_count_group_by() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let model_manager = get_shared_test_store().await;
+
+		// --- EXEC
+		let counts = model_manager
+			.active_store()
+			.exec_count_group_by("task", "project_id")
+			.await?;
+
+		// --- CHECK
+		assert_eq!(counts.len(), 2, "Number of projects with tasks");
+		assert!(
+			counts.values().all(|count| *count == 200),
+			"Number of tasks per project"
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_surreal_switch_and_remove_db() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let store = super::SurrealStore::new("memory").await?;
+		let other_store = store.with_db("other_db")?;
+		let filter_node = FilterNode::from(("name", "Project A"));
+		store
+			.exec_create("project", TestProjectForCreate { name: "Project A" })
+			.await?;
+
+		// --- CHECK - databases are isolated
+		let rs = other_store
+			.exec_select("project", Some(filter_node.clone()), ListOptions::default())
+			.await?;
+		assert_eq!(rs.len(), 0, "Number of projects in other_db");
+
+		// --- EXEC & CHECK - switch the database
+		store.set_db("other_db")?;
+		assert_eq!(store.db(), "other_db");
+		store
+			.exec_create("project", TestProjectForCreate { name: "Project A" })
+			.await?;
+		let rs = other_store
+			.exec_select("project", Some(filter_node.clone()), ListOptions::default())
+			.await?;
+		assert_eq!(rs.len(), 1, "Number of projects in other_db after switch");
+
+		// --- EXEC & CHECK - remove the database
+		store.set_db(super::DEFAULT_DB)?;
+		store.exec_remove_db("other_db").await?;
+		let rs = other_store
+			.exec_select("project", Some(filter_node), ListOptions::default())
+			.await?;
+		assert_eq!(rs.len(), 0, "Number of projects in removed other_db");
+
+		// --- CHECK - invalid names
+		assert!(store.set_db("other db; REMOVE NAMESPACE appns").is_err());
+		assert!(store.with_db("").is_err());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_surreal_create_all_transaction() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let store = super::SurrealStore::new("memory")
+			.await?
+			.with_db("create_all_db")?;
+		let record = |tid: &str, name: &str| {
+			let data: surrealdb::sql::Object =
+				crate::utils::map!["name".into() => name.into()].into();
+			(tid.to_string(), data)
+		};
+		let tid_1 = super::new_tid("project");
+		let tid_2 = super::new_tid("project");
+
+		// --- EXEC
+		let ids = store
+			.exec_create_all(vec![record(&tid_1, "P 1"), record(&tid_2, "P 2")])
+			.await?;
+
+		// --- CHECK
+		assert_eq!(ids, vec![tid_1.clone(), tid_2.clone()]);
+		let obj = store.exec_get("project", &tid_1).await?;
+		assert!(obj.get("ctime").is_some(), "ctime should be set");
+
+		// --- EXEC & CHECK - all or none (tid_1 already exists)
+		let tid_3 = super::new_tid("project");
+		let res = store
+			.exec_create_all(vec![record(&tid_3, "P 3"), record(&tid_1, "P 1 again")])
+			.await;
+		assert!(res.is_err(), "should fail on existing id");
+		let rs = store
+			.exec_select("project", None::<FilterNode>, ListOptions::default())
+			.await?;
+		assert_eq!(rs.len(), 2, "P 3 should not have been created");
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_surreal_merge_and_delete_many() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let store = super::SurrealStore::new("memory")
+			.await?
+			.with_db("many_db")?;
+		let mut tids = Vec::new();
+		for name in ["P 1", "P 2", "P 3"] {
+			tids.push(
+				store
+					.exec_create("project", TestProjectForCreate { name })
+					.await?,
+			);
+		}
+		let missing_tid = super::new_tid("project");
+
+		// --- EXEC & CHECK - get many (missing ids skipped)
+		let objs = store
+			.exec_get_many("project", &[tids[0].clone(), missing_tid.clone()])
+			.await?;
+		assert_eq!(objs.len(), 1);
+
+		// --- EXEC & CHECK - merge many
+		let ids = store
+			.exec_merge_many(
+				"project",
+				&tids[..2],
+				TestProjectForCreate { name: "Renamed" },
+			)
+			.await?;
+		assert_eq!(ids, tids[..2].to_vec());
+		let filter_node = FilterNode::from(("name", "Renamed"));
+		let rs = store
+			.exec_select("project", Some(filter_node), ListOptions::default())
+			.await?;
+		assert_eq!(rs.len(), 2, "Number of renamed projects");
+
+		// --- EXEC & CHECK - delete many
+		let ids = store
+			.exec_delete_many("project", &[tids[0].clone(), tids[2].clone(), missing_tid])
+			.await?;
+		assert_eq!(ids, vec![tids[0].clone(), tids[2].clone()]);
+		let rs = store
+			.exec_select("project", None::<FilterNode>, ListOptions::default())
+			.await?;
+		assert_eq!(rs.len(), 1, "Number of remaining projects");
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_surreal_id_not_of_table() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let store = super::SurrealStore::new("memory")
+			.await?
+			.with_db("id_table_db")?;
+		let tid = store
+			.exec_create("project", TestProjectForCreate { name: "P 1" })
+			.await?;
+		let task_tid = super::new_tid("task");
+
+		// --- EXEC & CHECK
+		let res = store.exec_get("task", &tid).await;
+		assert!(matches!(res, Err(crate::Error::StoreIdNotOfTable(_))));
+		let res = store
+			.exec_merge("project", &task_tid, TestProjectForCreate { name: "P 2" })
+			.await;
+		assert!(matches!(res, Err(crate::Error::StoreIdNotOfTable(_))));
+		let res = store.exec_delete("task", &tid).await;
+		assert!(matches!(res, Err(crate::Error::StoreIdNotOfTable(_))));
+		let tids = vec![tid.clone()];
+		let res = store.exec_get_many("task", &tids).await;
+		assert!(matches!(res, Err(crate::Error::StoreIdNotOfTable(_))));
+		let res = store
+			.exec_merge_many("task", &tids, TestProjectForCreate { name: "P 2" })
+			.await;
+		assert!(matches!(res, Err(crate::Error::StoreIdNotOfTable(_))));
+		let res = store.exec_delete_many("task", &tids).await;
+		assert!(matches!(res, Err(crate::Error::StoreIdNotOfTable(_))));
+		let rs = store
+			.exec_select("task", None::<FilterNode>, ListOptions::default())
+			.await?;
+		assert!(rs.is_empty(), "the merge should not have created a task");
+		store.exec_get("project", &tid).await?;
+
+		Ok(())
+	}
+
+	#[derive(serde::Serialize)]
+	struct TestProjectForCreate {
+		name: &'static str,
+	}
+	impl crate::model::store::Creatable for TestProjectForCreate {}
+	impl crate::model::store::Patchable for TestProjectForCreate {}
+
 	#[tokio::test]
 	async fn test_surreal_select_or() -> anyhow::Result<()> {
 		// --- FIXTURE
@@ -351,7 +860,7 @@ mod tests {
 
 		// --- EXEC
 		let rs = model_manager
-			.store()
+			.active_store()
 			.exec_select(
 				"task",
 				Some(vec![filter_nodes_1, filter_nodes_2]),
@@ -385,7 +894,7 @@ mod tests {
 
 		// --- EXEC
 		let rs = model_manager
-			.store()
+			.active_store()
 			.exec_select(
 				"task",
 				Some(vec![filter_nodes_1, filter_nodes_2]),
@@ -397,11 +906,11 @@ mod tests {
 		assert_eq!(rs.len(), 8, "Number of tasks ending with '11' OR '22'");
 		// TODO: Need to check the order
 
-		// for mut obj in rs.into_iter() {
+		// for obj in rs.into_iter() {
 		// 	println!(
 		// 		"{:?} {:?}",
-		// 		obj.x_take_val::<String>("title")?,
-		// 		obj.x_take_val::<bool>("done")?
+		// 		obj.get("title"),
+		// 		obj.get("done")
 		// 	);
 		// }
 
@@ -429,7 +938,7 @@ mod tests {
 
 		// --- EXEC
 		let mut rs = model_manager
-			.store()
+			.active_store()
 			.exec_select(
 				"task",
 				Some(vec![filter_nodes_1, filter_nodes_2]),
@@ -443,19 +952,19 @@ mod tests {
 		// Note: This will reverse order checked as we are usin pop.
 		assert_eq!(
 			"Task B.11",
-			rs.pop().unwrap().x_take_val::<String>("title")?
+			take_prop::<String>(&mut rs.pop().unwrap(), "title")?
 		);
 		assert_eq!(
 			"Task A.111",
-			rs.pop().unwrap().x_take_val::<String>("title")?
+			take_prop::<String>(&mut rs.pop().unwrap(), "title")?
 		);
 
 		// --- Visualy check results
-		// for mut obj in rs.into_iter() {
+		// for obj in rs.into_iter() {
 		// 	println!(
 		// 		"{:?} {:?}",
-		// 		obj.x_take_val::<String>("title")?,
-		// 		obj.x_take_val::<bool>("done")?
+		// 		obj.get("title"),
+		// 		obj.get("done")
 		// 	);
 		// }
 
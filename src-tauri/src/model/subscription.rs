@@ -0,0 +1,384 @@
+//! Live list subscriptions (e.g., the filtered tasks of the tasks data table).
+//!
+//! A subscription is an entity and a filter (the same as the `list_..` commands). The backend keeps its result rows,
+//! and on the Model events of the entity, lists them again and emits the difference as a `Subscription` hub event
+//! (topic: subscription id, label: `change`, data: `ListChangeData`), only when something changed.
+//! So, the list views do not have to re-query on any Model event.
+//!
+//! Notes:
+//!   - Only the entities of `list_rows` are supported (i.e., `project` and `task`).
+//!   - The refresh is done by the `SubscriptionRefresher` event bus listener, so the change event comes after the Model event.
+//!   - The task subscriptions are also refreshed on the comment events (i.e., for the `comment_count` of the rows).
+//!   - A failing refresh (e.g., store error) is logged, and does not stop the refresh of the other subscriptions.
+//!   - The subscriptions are of the active workspace, and are all dropped on workspace switch.
+//!   - The change events are only emitted to the window of the subscriber (i.e., `EventTarget::Origin` of its ctx).
+//!
+
+use super::{ProjectBmc, ProjectFilter, ProjectId, TaskBmc, TaskFilter, TaskId};
+use crate::ctx::Ctx;
+use crate::event::{AsyncHubListener, BoxFuture, EventTarget, HubEvent};
+use crate::prelude::*;
+use crate::{Error, Result};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use ts_rs::TS;
+
+// region:    --- Subscription Types
+
+/// Returned by `subscribe_list`, with the current rows of the list.
+#[derive(TS, Serialize, Clone)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct ListSubscriptionData<E: Serialize + Clone> {
+	pub id: String,
+	pub rows: Vec<E>,
+}
+
+/// Data of the `Subscription` hub events, the changes of the rows since the last event (or the subscribe).
+#[derive(TS, Serialize, Clone, Debug, PartialEq)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct ListChangeData<E: Serialize + Clone> {
+	/// The rows now matching the filter (in the list order)
+	pub entered: Vec<E>,
+	/// The ids of the rows not matching the filter anymore (e.g., deleted)
+	pub left: Vec<String>,
+	/// The rows still matching the filter, but with some changed properties
+	pub changed: Vec<E>,
+}
+
+impl<E: Serialize + Clone> ListChangeData<E> {
+	fn is_empty(&self) -> bool {
+		self.entered.is_empty() && self.left.is_empty() && self.changed.is_empty()
+	}
+}
+
+struct ListSubscription {
+	entity: String,
+	filter: Option<JsonValue>,
+	rows: Vec<JsonValue>,
+	/// The window of the subscriber, to emit the changes to (all windows when none)
+	window_label: Option<String>,
+}
+
+/// The list subscriptions of the active workspace (held by the `ModelStore`).
+///
+/// Note: The async mutex is held while listing, so the refreshes (and subscribes) are serialized.
+#[derive(Default)]
+pub struct ListSubscriptions {
+	next_id: AtomicU64,
+	subs: Mutex<HashMap<String, ListSubscription>>,
+}
+
+impl ListSubscriptions {
+	pub(in crate::model) async fn clear(&self) {
+		self.subs.lock().await.clear();
+	}
+}
+
+// endregion: --- Subscription Types
+
+// region:    --- SubscriptionBmc
+
+pub struct SubscriptionBmc;
+
+impl SubscriptionBmc {
+	/// Subscribe to the rows of the entity matching the filter (e.g., `{"project_id": "project:.."}` for `task`).
+	pub async fn subscribe_list(
+		ctx: Arc<Ctx>,
+		entity: &str,
+		filter: Option<JsonValue>,
+	) -> Result<ListSubscriptionData<JsonValue>> {
+		let model_manager = ctx.get_model_manager();
+		let subscriptions = model_manager.subscriptions();
+
+		let mut subs = subscriptions.subs.lock().await;
+		let rows = list_rows(ctx.clone(), entity, filter.clone()).await?;
+		let id = f!(
+			"sub:{}",
+			subscriptions.next_id.fetch_add(1, Ordering::Relaxed) + 1
+		);
+		subs.insert(
+			id.clone(),
+			ListSubscription {
+				entity: entity.to_string(),
+				filter,
+				rows: rows.clone(),
+				window_label: ctx.window_label().map(String::from),
+			},
+		);
+
+		Ok(ListSubscriptionData { id, rows })
+	}
+
+	/// Note: Unsubscribing an unknown (or already removed) subscription is not an error.
+	pub async fn unsubscribe(ctx: Arc<Ctx>, id: &str) -> Result<()> {
+		let model_manager = ctx.get_model_manager();
+		model_manager.subscriptions().subs.lock().await.remove(id);
+		Ok(())
+	}
+
+	/// List again the rows of the subscriptions depending on the entity (of a Model event), and emit their changes.
+	async fn refresh(ctx: Arc<Ctx>, entity: &str) {
+		let model_manager = ctx.get_model_manager();
+		let mut subs = model_manager.subscriptions().subs.lock().await;
+
+		// Note: The events of a call started before a workspace switch do not refresh the new workspace subscriptions.
+		if ctx.workspace_db() != model_manager.active_db() {
+			return;
+		}
+
+		for (id, sub) in subs
+			.iter_mut()
+			.filter(|(_, sub)| rows_depend_on(&sub.entity, entity))
+		{
+			let rows = match list_rows(ctx.clone(), &sub.entity, sub.filter.clone()).await {
+				Ok(rows) => rows,
+				Err(err) => {
+					tracing::warn!(subscription = %id, error = %err, "subscription refresh failed");
+					continue;
+				}
+			};
+			let change = diff_rows(&sub.rows, &rows);
+			sub.rows = rows;
+
+			if !change.is_empty() {
+				// Note: Emitted as of the subscriber (i.e., its window is the origin), not of the refreshing call.
+				let sub_ctx = match &sub.window_label {
+					Some(label) => ctx.with_window_label(label.clone()),
+					None => ctx.without_window_label(),
+				};
+				sub_ctx.emit_hub_event(HubEvent {
+					hub: "Subscription".to_string(),
+					topic: id.clone(),
+					label: Some("change".to_string()),
+					seq: None,
+					request_id: None,
+					data: Some(change),
+					target: EventTarget::Origin,
+				});
+			}
+		}
+	}
+}
+
+// endregion: --- SubscriptionBmc
+
+// region:    --- SubscriptionRefresher
+
+/// Event bus listener refreshing the list subscriptions of the entity of each `Model` event.
+pub struct SubscriptionRefresher;
+
+impl AsyncHubListener for SubscriptionRefresher {
+	fn on_event(&self, ctx: Arc<Ctx>, event: Arc<HubEvent<JsonValue>>) -> BoxFuture {
+		Box::pin(async move {
+			if event.hub == "Model" {
+				SubscriptionBmc::refresh(ctx, &event.topic).await;
+			}
+		})
+	}
+}
+
+// endregion: --- SubscriptionRefresher
+
+// region:    --- Subscription Utils
+
+/// If the rows of the subscription entity depend on the records of the entity (e.g., the `comment_count` of the tasks).
+fn rows_depend_on(sub_entity: &str, entity: &str) -> bool {
+	sub_entity == entity || (sub_entity == "task" && entity == "comment")
+}
+
+/// List the rows (as JSON) of a subscription, with the list of the entity Bmc.
+async fn list_rows(
+	ctx: Arc<Ctx>,
+	entity: &str,
+	filter: Option<JsonValue>,
+) -> Result<Vec<JsonValue>> {
+	let rows = match entity {
+		ProjectId::TABLE => {
+			let filter: Option<ProjectFilter> = filter.map(serde_json::from_value).transpose()?;
+			serde_json::to_value(ProjectBmc::list(ctx, filter).await?)?
+		}
+		TaskId::TABLE => {
+			let filter: Option<TaskFilter> = filter.map(serde_json::from_value).transpose()?;
+			serde_json::to_value(TaskBmc::list(ctx, filter).await?)?
+		}
+		_ => return Err(Error::SubscriptionEntityNotSupported(entity.to_string())),
+	};
+
+	match rows {
+		JsonValue::Array(rows) => Ok(rows),
+		_ => Err(Error::XValueNotOfType("Array")),
+	}
+}
+
+/// The changes from the `old` to the `new` rows, by their `id`.
+fn diff_rows(old: &[JsonValue], new: &[JsonValue]) -> ListChangeData<JsonValue> {
+	let old_by_id: HashMap<&str, &JsonValue> = old
+		.iter()
+		.filter_map(|row| row_id(row).map(|id| (id, row)))
+		.collect();
+	let new_ids: HashSet<&str> = new.iter().filter_map(row_id).collect();
+
+	let mut entered = Vec::new();
+	let mut changed = Vec::new();
+	for row in new {
+		match row_id(row).and_then(|id| old_by_id.get(id)) {
+			None => entered.push(row.clone()),
+			Some(old_row) if *old_row != row => changed.push(row.clone()),
+			Some(_) => (),
+		}
+	}
+
+	let left = old
+		.iter()
+		.filter_map(row_id)
+		.filter(|id| !new_ids.contains(id))
+		.map(String::from)
+		.collect();
+
+	ListChangeData {
+		entered,
+		left,
+		changed,
+	}
+}
+
+fn row_id(row: &JsonValue) -> Option<&str> {
+	row.get("id").and_then(|id| id.as_str())
+}
+
+// endregion: --- Subscription Utils
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_utils::{create_test_project, new_test_ctx, task_for_create};
+	use crate::model::{CommentBmc, CommentForCreate, TaskForUpdate};
+	use serde_json::json;
+
+	#[test]
+	fn test_subscription_diff_rows() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let old = vec![
+			json!({"id": "task:1", "title": "A"}),
+			json!({"id": "task:2", "title": "B"}),
+			json!({"id": "task:3", "title": "C"}),
+		];
+		let new = vec![
+			json!({"id": "task:4", "title": "D"}),
+			json!({"id": "task:1", "title": "A"}),
+			json!({"id": "task:3", "title": "C2"}),
+		];
+
+		// --- EXEC
+		let change = diff_rows(&old, &new);
+
+		// --- CHECK
+		assert_eq!(change.entered, vec![json!({"id": "task:4", "title": "D"})]);
+		assert_eq!(change.left, vec!["task:2".to_string()]);
+		assert_eq!(change.changed, vec![json!({"id": "task:3", "title": "C2"})]);
+		assert!(diff_rows(&new, &new).is_empty());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_subscription_task_list_events() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, events) = new_test_ctx().await?;
+		let project_id = create_test_project(ctx.clone(), "Project 01").await?;
+		let id_01 = TaskBmc::create(ctx.clone(), task_for_create(&project_id, "Task 01"))
+			.await?
+			.id;
+		let id_02 = TaskBmc::create(ctx.clone(), task_for_create(&project_id, "Task 02"))
+			.await?
+			.id;
+
+		// --- EXEC - open tasks of the project
+		let filter = json!({"project_id": project_id, "done": false});
+		let sub = SubscriptionBmc::subscribe_list(ctx.clone(), "task", Some(filter)).await?;
+
+		// --- CHECK
+		assert_eq!(sub.rows.len(), 2);
+
+		// --- EXEC - done task leaves the list
+		let data = TaskForUpdate {
+			title: None,
+			done: Some(true),
+			desc: None,
+			due_date: None,
+			recur_rule: None,
+		};
+		TaskBmc::update(ctx.clone(), &id_01, data).await?;
+
+		// --- CHECK
+		let evts = events.wait_for("Subscription").await;
+		assert_eq!(evts.len(), 1);
+		assert_eq!(evts[0].topic, sub.id);
+		assert_eq!(evts[0].label.as_deref(), Some("change"));
+		let change = evts[0].data.as_ref().unwrap();
+		assert_eq!(change["left"], json!([id_01]));
+		assert_eq!(change["entered"], json!([]));
+
+		// --- EXEC & CHECK - comment changes the comment count of the row
+		let data = CommentForCreate {
+			task_id: TaskId::from_store(id_02.clone()),
+			body: "Comment 01".to_string(),
+		};
+		CommentBmc::create(ctx.clone(), data).await?;
+		let evts = events.wait_for("Subscription").await;
+		let change = evts[0].data.as_ref().unwrap();
+		assert_eq!(change["changed"][0]["id"], json!(id_02));
+		assert_eq!(change["changed"][0]["comment_count"], 1);
+
+		// --- EXEC & CHECK - no event after unsubscribe
+		SubscriptionBmc::unsubscribe(ctx.clone(), &sub.id).await?;
+		TaskBmc::delete(ctx.clone(), &id_02).await?;
+		// Note: The refresh (as the listener does) of the task subscriptions, none anymore.
+		SubscriptionBmc::refresh(ctx.clone(), "task").await;
+		assert!(events.take("Subscription").is_empty());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_subscription_events_to_subscriber() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, events) = new_test_ctx().await?;
+		let main_ctx = ctx.with_window_label("main");
+		let other_ctx = ctx.with_window_label("project-window/other");
+		let project_id = create_test_project(ctx.clone(), "Project 01").await?;
+		let sub = SubscriptionBmc::subscribe_list(main_ctx, "task", None).await?;
+		let headless_sub = SubscriptionBmc::subscribe_list(ctx.clone(), "task", None).await?;
+
+		// --- EXEC - task created from another window
+		TaskBmc::create(other_ctx, task_for_create(&project_id, "Task 01")).await?;
+
+		// --- CHECK - change events to the subscriber window, or all of the windows when headless
+		let mut evts = Vec::new();
+		for _ in 0..2 {
+			evts.extend(events.wait_for("Subscription").await);
+			if evts.len() == 2 {
+				break;
+			}
+		}
+		assert_eq!(evts.len(), 2);
+		let target_of = |id: &str| {
+			evts.iter()
+				.find(|evt| evt.topic == id)
+				.map(|evt| evt.target.clone())
+		};
+		assert_eq!(
+			target_of(&sub.id),
+			Some(EventTarget::Window("main".to_string()))
+		);
+		assert_eq!(target_of(&headless_sub.id), Some(EventTarget::All));
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
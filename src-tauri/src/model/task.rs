@@ -1,50 +1,48 @@
 //! All model and controller for the Item type
 //!
 
-use super::bmc_base::{bmc_create, bmc_delete, bmc_get, bmc_list, bmc_update};
-use super::store::{Creatable, Filterable, Patchable};
-use super::ModelMutateResultData;
+use super::bmc_base::{bmc_create, bmc_delete, bmc_list, bmc_update, changed_props};
+use super::recur::{format_date, parse_date, RecurRule};
+use super::store::{from_object, new_tid, Filterable, Nanos};
+use super::validate::{Validate, Violations, TEXT_RULE, TITLE_RULE};
+use super::{
+	fire_model_event, AttachmentBmc, CommentBmc, ModelEventData, ModelManyEventData,
+	ModelMutateManyResultData, ModelMutateResultData, ProjectBmc, ProjectId, TaskDepBmc, TaskId,
+	TimeEntryBmc,
+};
 use crate::ctx::Ctx;
-use crate::utils::{map, XTake};
 use crate::{Error, Result};
-use modql::filter::{FilterNodes, OpValsString};
+use app_macros::{Bmc, Creatable, Patchable};
+use modql::filter::{FilterNode, FilterNodes, OpValBool, OpValsBool, OpValsString};
 use modql::ListOptions;
 use serde::{Deserialize, Serialize};
 use serde_with_macros::skip_serializing_none;
-use std::collections::BTreeMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use surrealdb::sql::{Object, Value};
+use surrealdb::sql::Object;
 use ts_rs::TS;
 
 // region:    --- Task
 
 #[skip_serializing_none]
-#[derive(Serialize, TS, Debug)]
+#[derive(Serialize, Deserialize, TS, Debug, Clone)]
 #[ts(export, export_to = "../src-ui/src/bindings/")]
 pub struct Task {
-	pub id: String,
-	pub ctime: String,
-	pub project_id: String,
+	pub id: TaskId,
+	pub ctime: Nanos,
+	pub project_id: ProjectId,
 
 	pub done: bool,
 	pub title: String,
 	pub desc: Option<String>,
-}
 
-impl TryFrom<Object> for Task {
-	type Error = Error;
-	fn try_from(mut val: Object) -> Result<Task> {
-		let task = Task {
-			id: val.x_take_val("id")?,
-			ctime: val.x_take_val::<i64>("ctime")?.to_string(),
-			project_id: val.x_take_val("project_id")?,
-			done: val.x_take_val("done")?,
-			title: val.x_take_val("title")?,
-			desc: val.x_take("desc")?,
-		};
+	/// Date as `YYYY-MM-DD`
+	pub due_date: Option<String>,
+	/// RRULE subset (see `model::recur`), e.g., `FREQ=WEEKLY;BYDAY=MO`
+	pub recur_rule: Option<String>,
 
-		Ok(task)
-	}
+	/// Only set for list results
+	pub comment_count: Option<u32>,
 }
 
 // endregion: --- Task
@@ -52,65 +50,47 @@ impl TryFrom<Object> for Task {
 // region:    --- TaskForCreate
 
 #[skip_serializing_none]
-#[derive(Deserialize, TS, Debug)]
+#[derive(Serialize, Deserialize, TS, Debug, Creatable)]
 #[ts(export, export_to = "../src-ui/src/bindings/")]
 pub struct TaskForCreate {
-	pub project_id: String,
+	pub project_id: ProjectId,
 	pub title: String,
+	#[store(default = false)]
 	pub done: Option<bool>,
 	pub desc: Option<String>,
+	pub due_date: Option<String>,
+	pub recur_rule: Option<String>,
 }
 
-impl From<TaskForCreate> for Value {
-	fn from(val: TaskForCreate) -> Self {
-		let mut data = map![
-			"project_id".into() => val.project_id.into(),
-			"title".into() => val.title.into(),
-		];
-
-		// default for done is false
-		data.insert("done".into(), val.done.unwrap_or(false).into());
-
-		if let Some(desc) = val.desc {
-			data.insert("desc".into(), desc.into());
-		}
-		Value::Object(data.into())
+impl Validate for TaskForCreate {
+	fn check(&self, violations: &mut Violations) {
+		violations.text("title", &self.title, TITLE_RULE);
+		violations.opt_text("desc", self.desc.as_deref(), TEXT_RULE);
 	}
 }
 
-impl Creatable for TaskForCreate {}
-
 // endregion: --- TaskForCreate
 
 // region:    --- TaskForUpdate
 
 #[skip_serializing_none]
-#[derive(Deserialize, TS, Debug)]
+#[derive(Serialize, Deserialize, TS, Debug, Patchable)]
 #[ts(export, export_to = "../src-ui/src/bindings/")]
 pub struct TaskForUpdate {
 	pub title: Option<String>,
 	pub done: Option<bool>,
 	pub desc: Option<String>,
+	pub due_date: Option<String>,
+	pub recur_rule: Option<String>,
 }
 
-impl From<TaskForUpdate> for Value {
-	fn from(val: TaskForUpdate) -> Self {
-		let mut data = BTreeMap::new();
-		if let Some(title) = val.title {
-			data.insert("title".into(), title.into());
-		}
-		if let Some(done) = val.done {
-			data.insert("done".into(), done.into());
-		}
-		if let Some(desc) = val.desc {
-			data.insert("desc".into(), desc.into());
-		}
-		Value::Object(data.into())
+impl Validate for TaskForUpdate {
+	fn check(&self, violations: &mut Violations) {
+		violations.opt_text("title", self.title.as_deref(), TITLE_RULE);
+		violations.opt_text("desc", self.desc.as_deref(), TEXT_RULE);
 	}
 }
 
-impl Patchable for TaskForUpdate {}
-
 // endregion: --- TaskForUpdate
 
 // region:    --- TaskFilter
@@ -119,37 +99,84 @@ impl Patchable for TaskForUpdate {}
 pub struct TaskFilter {
 	pub project_id: Option<OpValsString>,
 	pub title: Option<OpValsString>,
+	pub done: Option<OpValsBool>,
 }
 
 impl Filterable for TaskFilter {}
 
+/// Tasks of the bulk operations (see `TaskBmc::update_many`), as `{"filter": {..}}` or `{"ids": [..]}`.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskTarget {
+	Filter(TaskFilter),
+	Ids(Vec<String>),
+}
+
 // endregion: --- TaskFilter
 
 // region:    --- TaskBmc
 
+/// Note: Only `get` is generated (see `app_macros::Bmc`), the other CRUD methods have task rules.
+///       The CRUD commands are generated on top of them (see `task_commands`).
+#[derive(Bmc)]
+#[bmc(
+	table = "task",
+	entity = Task,
+	for_create = TaskForCreate,
+	for_update = TaskForUpdate,
+	custom(create, update, delete, list),
+	ipc(id = TaskId)
+)]
 pub struct TaskBmc;
 
 impl TaskBmc {
-	const ENTITY: &'static str = "task";
-
-	pub async fn get(ctx: Arc<Ctx>, id: &str) -> Result<Task> {
-		bmc_get::<Task>(ctx, Self::ENTITY, id).await
-	}
-
 	pub async fn create(ctx: Arc<Ctx>, data: TaskForCreate) -> Result<ModelMutateResultData> {
-		bmc_create(ctx, Self::ENTITY, data).await
+		validate_dates(data.due_date.as_deref(), data.recur_rule.as_deref())?;
+		ProjectBmc::ensure_not_archived(ctx.clone(), &data.project_id).await?;
+		bmc_create::<Task, _>(ctx, Self::ENTITY, data).await
 	}
 
+	/// Notes:
+	///   - Tasks of archived projects are read-only.
+	///   - A task cannot be marked done while some of its blockers (see `TaskDepBmc`) are still open.
+	///   - When a recurring task goes from open to done, its next occurrence is created
+	///     (which fires its own `Model`/`task` create event).
 	pub async fn update(
 		ctx: Arc<Ctx>,
 		id: &str,
 		data: TaskForUpdate,
 	) -> Result<ModelMutateResultData> {
-		bmc_update(ctx, Self::ENTITY, id, data).await
+		validate_dates(data.due_date.as_deref(), data.recur_rule.as_deref())?;
+
+		let task = Self::get_writable(ctx.clone(), id).await?;
+
+		let completing = data.done == Some(true) && !task.done;
+
+		if completing {
+			let open_blocker_ids = TaskDepBmc::open_blockers(ctx.clone(), id).await?;
+			if !open_blocker_ids.is_empty() {
+				return Err(Error::TaskBlocked(open_blocker_ids));
+			}
+		}
+
+		let result_data = bmc_update::<Task, _>(ctx.clone(), Self::ENTITY, id, data).await?;
+
+		if completing {
+			let task = Self::get(ctx.clone(), id).await?;
+			Self::create_next_occurrence(ctx, task).await?;
+		}
+
+		Ok(result_data)
 	}
 
 	pub async fn delete(ctx: Arc<Ctx>, id: &str) -> Result<ModelMutateResultData> {
-		bmc_delete(ctx, Self::ENTITY, id).await
+		Self::get_writable(ctx.clone(), id).await?;
+
+		TaskDepBmc::delete_for_task(ctx.clone(), id).await?;
+		CommentBmc::delete_for_task(ctx.clone(), id).await?;
+		AttachmentBmc::delete_for_task(ctx.clone(), id).await?;
+		TimeEntryBmc::delete_for_task(ctx.clone(), id).await?;
+		bmc_delete::<Task>(ctx, Self::ENTITY, id).await
 	}
 
 	pub async fn list(ctx: Arc<Ctx>, filter: Option<TaskFilter>) -> Result<Vec<Task>> {
@@ -158,8 +185,461 @@ impl TaskBmc {
 			offset: None,
 			order_bys: Some("!ctime".into()),
 		};
-		bmc_list(ctx, Self::ENTITY, filter, opts).await
+		let mut tasks: Vec<Task> = bmc_list(ctx.clone(), Self::ENTITY, filter, opts).await?;
+
+		let comment_counts = CommentBmc::count_by_task(ctx).await?;
+		for task in tasks.iter_mut() {
+			task.comment_count = Some(comment_counts.get(&*task.id).copied().unwrap_or(0));
+		}
+
+		Ok(tasks)
+	}
+
+	/// Apply the same patch to all of the target tasks, in one store statement,
+	/// and fire a single `Model`/`task`/`update_many` event (with each updated task, see `ModelManyEventData`).
+	///
+	/// Note: The rules of `update` apply to each task. A task can be completed along with its open blockers.
+	pub async fn update_many(
+		ctx: Arc<Ctx>,
+		target: TaskTarget,
+		data: TaskForUpdate,
+	) -> Result<ModelMutateManyResultData> {
+		data.validate()?;
+		validate_dates(data.due_date.as_deref(), data.recur_rule.as_deref())?;
+
+		let tasks = Self::list_target(ctx.clone(), target).await?;
+		Self::ensure_projects_not_archived(ctx.clone(), &tasks).await?;
+
+		let completing_ids: HashSet<String> = match data.done {
+			Some(true) => tasks
+				.iter()
+				.filter(|t| !t.done)
+				.map(|t| t.id.to_string())
+				.collect(),
+			_ => HashSet::new(),
+		};
+
+		if !completing_ids.is_empty() {
+			let open_blocker_ids =
+				Self::open_blockers_outside(ctx.clone(), &completing_ids).await?;
+			if !open_blocker_ids.is_empty() {
+				return Err(Error::TaskBlocked(open_blocker_ids));
+			}
+		}
+
+		let ids: Vec<String> = tasks.into_iter().map(|t| t.id.into()).collect();
+		let model_manager = ctx.get_model_manager();
+		let store = model_manager.store(&ctx);
+		let mut befores: HashMap<String, Object> = HashMap::new();
+		for obj in store.exec_get_many(Self::ENTITY, &ids).await? {
+			befores.insert(object_id(&obj), obj);
+		}
+		let ids = store.exec_merge_many(Self::ENTITY, &ids, data).await?;
+
+		let mut items = Vec::new();
+		let mut completed = Vec::new();
+		for after in store.exec_get_many(Self::ENTITY, &ids).await? {
+			let id = object_id(&after);
+			let changed = befores
+				.get(&id)
+				.map(|before| changed_props(before, &after))
+				.unwrap_or_default();
+			let task: Task = from_object(after)?;
+			if completing_ids.contains(&id) {
+				completed.push(task.clone());
+			}
+			items.push(ModelEventData::updated(id, task, changed));
+		}
+		fire_model_event(
+			&ctx,
+			Self::ENTITY,
+			"update_many",
+			ModelManyEventData {
+				ids: ids.clone(),
+				items,
+			},
+		);
+
+		for task in completed {
+			Self::create_next_occurrence(ctx.clone(), task).await?;
+		}
+
+		Ok(ModelMutateManyResultData::from(ids))
+	}
+
+	/// Delete all of the target tasks (with their dependencies, comments, ...), in one store statement,
+	/// and fire a single `Model`/`task`/`delete_many` event (none for the dependencies, comments, ...).
+	pub async fn delete_many(
+		ctx: Arc<Ctx>,
+		target: TaskTarget,
+	) -> Result<ModelMutateManyResultData> {
+		let tasks = Self::list_target(ctx.clone(), target).await?;
+		Self::ensure_projects_not_archived(ctx.clone(), &tasks).await?;
+
+		let ids: Vec<String> = tasks.into_iter().map(|t| t.id.into()).collect();
+		TaskDepBmc::delete_for_tasks(ctx.clone(), &ids).await?;
+		CommentBmc::delete_for_tasks(ctx.clone(), &ids).await?;
+		AttachmentBmc::delete_for_tasks(ctx.clone(), &ids).await?;
+		TimeEntryBmc::delete_for_tasks(ctx.clone(), &ids).await?;
+
+		let ids = ctx
+			.get_model_manager()
+			.store(&ctx)
+			.exec_delete_many(Self::ENTITY, &ids)
+			.await?;
+
+		let result_data = ModelMutateManyResultData::from(ids);
+		fire_model_event(&ctx, Self::ENTITY, "delete_many", result_data.clone());
+
+		Ok(result_data)
+	}
+
+	/// List the open tasks (matching the optional filter) which do not have any open blockers.
+	pub async fn list_actionable(ctx: Arc<Ctx>, filter: Option<TaskFilter>) -> Result<Vec<Task>> {
+		let tasks = Self::list(ctx.clone(), filter).await?;
+
+		// ids of all of the open tasks, to know which blockers are still open
+		let open_filter = TaskFilter {
+			project_id: None,
+			title: None,
+			done: Some(OpValBool::Eq(false).into()),
+		};
+		let open_ids: HashSet<String> = Self::list(ctx.clone(), Some(open_filter))
+			.await?
+			.into_iter()
+			.map(|t| t.id.into())
+			.collect();
+
+		let blocked_ids: HashSet<String> = TaskDepBmc::list(ctx, None)
+			.await?
+			.into_iter()
+			.filter(|dep| open_ids.contains(&*dep.blocker_id))
+			.map(|dep| dep.task_id.into())
+			.collect();
+
+		Ok(tasks
+			.into_iter()
+			.filter(|t| !t.done && !blocked_ids.contains(&*t.id))
+			.collect())
+	}
+
+	/// The existing tasks of a bulk operation target.
+	async fn list_target(ctx: Arc<Ctx>, target: TaskTarget) -> Result<Vec<Task>> {
+		match target {
+			TaskTarget::Filter(filter) => {
+				bmc_list(ctx, Self::ENTITY, Some(filter), ListOptions::default()).await
+			}
+			// Note: The ids of other tables (e.g., a `comment:..`) are errors, rather than skipped.
+			TaskTarget::Ids(ids) => ctx
+				.get_model_manager()
+				.store(&ctx)
+				.exec_get_many(Self::ENTITY, &ids)
+				.await?
+				.into_iter()
+				.map(from_object)
+				.collect(),
+		}
+	}
+
+	/// The task, failing with `Error::ProjectArchived` if its project is archived (i.e., the task is read-only),
+	/// for the changes of the task or of its comments, attachments, ...
+	pub(super) async fn get_writable(ctx: Arc<Ctx>, id: &str) -> Result<Task> {
+		let task = Self::get(ctx.clone(), id).await?;
+		ProjectBmc::ensure_not_archived(ctx, &task.project_id).await?;
+		Ok(task)
+	}
+
+	async fn ensure_projects_not_archived(ctx: Arc<Ctx>, tasks: &[Task]) -> Result<()> {
+		let project_ids: HashSet<&str> = tasks.iter().map(|t| &*t.project_id).collect();
+		for project_id in project_ids {
+			ProjectBmc::ensure_not_archived(ctx.clone(), project_id).await?;
+		}
+		Ok(())
+	}
+
+	/// Ids of the open blockers of the tasks, which are not themselves in `task_ids`.
+	async fn open_blockers_outside(
+		ctx: Arc<Ctx>,
+		task_ids: &HashSet<String>,
+	) -> Result<Vec<String>> {
+		let open_filter = TaskFilter {
+			project_id: None,
+			title: None,
+			done: Some(OpValBool::Eq(false).into()),
+		};
+		let open_ids: HashSet<String> = bmc_list::<Task, _>(
+			ctx.clone(),
+			Self::ENTITY,
+			Some(open_filter),
+			ListOptions::default(),
+		)
+		.await?
+		.into_iter()
+		.map(|t| t.id.into())
+		.collect();
+
+		let mut blocker_ids: Vec<String> = TaskDepBmc::list(ctx, None)
+			.await?
+			.into_iter()
+			.filter(|dep| task_ids.contains(&*dep.task_id))
+			.filter(|dep| {
+				open_ids.contains(&*dep.blocker_id) && !task_ids.contains(&*dep.blocker_id)
+			})
+			.map(|dep| dep.blocker_id.into())
+			.collect();
+		blocker_ids.sort();
+		blocker_ids.dedup();
+
+		Ok(blocker_ids)
+	}
+
+	/// Records (for `SurrealStore::exec_create_all`) copying all of the tasks of a project to another project,
+	/// with new ids (used by `ProjectBmc::clone_project`).
+	pub(super) async fn records_for_clone(
+		ctx: Arc<Ctx>,
+		from_project_id: &str,
+		to_project_id: &str,
+		reset_done: bool,
+		strip_desc: bool,
+	) -> Result<Vec<(String, Object)>> {
+		let filter = FilterNode::from(("project_id", from_project_id));
+		let opts = ListOptions {
+			limit: None,
+			offset: None,
+			order_bys: Some("ctime".into()),
+		};
+		let objects = ctx
+			.get_model_manager()
+			.store(&ctx)
+			.exec_select(Self::ENTITY, Some(filter), opts)
+			.await?;
+
+		let records = objects
+			.into_iter()
+			.map(|mut obj| {
+				obj.remove("id");
+				obj.remove("ctime");
+				obj.insert("project_id".into(), to_project_id.into());
+				if reset_done {
+					obj.insert("done".into(), false.into());
+				}
+				if strip_desc {
+					obj.remove("desc");
+				}
+				(new_tid(Self::ENTITY), obj)
+			})
+			.collect();
+
+		Ok(records)
+	}
+
+	/// Create the next occurrence of a recurring task, shifted from its due date (or today if none).
+	/// Returns None if the task is not recurring or if its rule ended.
+	async fn create_next_occurrence(
+		ctx: Arc<Ctx>,
+		task: Task,
+	) -> Result<Option<ModelMutateResultData>> {
+		let recur_rule = match task.recur_rule {
+			Some(recur_rule) => recur_rule,
+			None => return Ok(None),
+		};
+		let rule: RecurRule = recur_rule.parse()?;
+
+		let from = match task.due_date {
+			Some(due_date) => parse_date(&due_date)?,
+			None => chrono::Local::now().date_naive(),
+		};
+
+		let next_date = match rule.next_after(from) {
+			Some(next_date) => next_date,
+			None => return Ok(None),
+		};
+
+		let next_task = TaskForCreate {
+			project_id: task.project_id,
+			title: task.title,
+			done: Some(false),
+			desc: task.desc,
+			due_date: Some(format_date(next_date)),
+			recur_rule: Some(recur_rule),
+		};
+
+		Self::create(ctx, next_task).await.map(Some)
 	}
 }
 
+/// Fail early on malformed dates or rules rather than when the task gets completed.
+fn validate_dates(due_date: Option<&str>, recur_rule: Option<&str>) -> Result<()> {
+	if let Some(due_date) = due_date {
+		parse_date(due_date)?;
+	}
+	if let Some(recur_rule) = recur_rule {
+		recur_rule.parse::<RecurRule>()?;
+	}
+	Ok(())
+}
+
+/// The id of a store object (e.g., `task:xz4u..`), as on its entity.
+fn object_id(obj: &Object) -> String {
+	obj.get("id").map(|id| id.to_string()).unwrap_or_default()
+}
+
 // endregion: --- TaskBmc
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::model::test_utils::{create_test_project, new_test_ctx, task_for_create};
+	use crate::model::{CommentForCreate, TaskDepForCreate};
+	use serde_json::json;
+
+	fn done_data(done: bool) -> TaskForUpdate {
+		TaskForUpdate {
+			title: None,
+			done: Some(done),
+			desc: None,
+			due_date: None,
+			recur_rule: None,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_task_bmc_complete_recurring() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, events) = new_test_ctx().await?;
+		let project_id = create_test_project(ctx.clone(), "Project 01").await?;
+		let data = TaskForCreate {
+			due_date: Some("2023-01-31".to_string()),
+			recur_rule: Some("FREQ=DAILY".to_string()),
+			..task_for_create(&project_id, "Task 01")
+		};
+		let id = TaskBmc::create(ctx.clone(), data).await?.id;
+		events.take("Model");
+
+		// --- EXEC
+		TaskBmc::update(ctx.clone(), &id, done_data(true)).await?;
+
+		// --- CHECK
+		let evts = events.take("Model");
+		assert_eq!(evts.len(), 2);
+		assert_eq!(evts[0].label.as_deref(), Some("update"));
+		let data = evts[0].data.as_ref().unwrap();
+		assert_eq!(data["id"], json!(id));
+		assert_eq!(data["changed"], json!(["done"]));
+		assert_eq!(evts[1].label.as_deref(), Some("create"));
+		let next = &evts[1].data.as_ref().unwrap()["entity"];
+		assert_eq!(next["due_date"], "2023-02-01");
+		assert_eq!(next["done"], false);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_task_bmc_archived_project() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, events) = new_test_ctx().await?;
+		let project_id = create_test_project(ctx.clone(), "Project 01").await?;
+		let id = TaskBmc::create(ctx.clone(), task_for_create(&project_id, "Task 01"))
+			.await?
+			.id;
+		ProjectBmc::archive(ctx.clone(), &project_id).await?;
+		events.take("Model");
+
+		// --- EXEC
+		let create_res =
+			TaskBmc::create(ctx.clone(), task_for_create(&project_id, "Task 02")).await;
+		let update_res = TaskBmc::update(ctx.clone(), &id, done_data(true)).await;
+		let delete_res = TaskBmc::delete(ctx.clone(), &id).await;
+
+		// --- CHECK
+		assert!(matches!(create_res, Err(Error::ProjectArchived(_))));
+		assert!(matches!(update_res, Err(Error::ProjectArchived(_))));
+		assert!(matches!(delete_res, Err(Error::ProjectArchived(_))));
+		assert!(events.take("Model").is_empty(), "no event when failing");
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_task_bmc_update_many() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, events) = new_test_ctx().await?;
+		let project_id = create_test_project(ctx.clone(), "Project 01").await?;
+		let mut ids = Vec::new();
+		for title in ["Task 01", "Task 02", "Task 03"] {
+			ids.push(
+				TaskBmc::create(ctx.clone(), task_for_create(&project_id, title))
+					.await?
+					.id,
+			);
+		}
+		events.take("Model");
+
+		// --- EXEC
+		let target = TaskTarget::Ids(ids[..2].to_vec());
+		let result = TaskBmc::update_many(ctx.clone(), target, done_data(true)).await?;
+
+		// --- CHECK
+		assert_eq!(result.ids.len(), 2);
+		let evts = events.take("Model");
+		assert_eq!(evts.len(), 1);
+		assert_eq!(evts[0].label.as_deref(), Some("update_many"));
+		let data = evts[0].data.as_ref().unwrap();
+		assert_eq!(data["ids"], json!(ids[..2]));
+		assert_eq!(data["items"][0]["id"], json!(ids[0]));
+		assert_eq!(data["items"][0]["entity"]["done"], true);
+		assert_eq!(data["items"][1]["changed"], json!(["done"]));
+		let filter: TaskFilter = serde_json::from_value(json!({"done": true}))?;
+		assert_eq!(TaskBmc::list(ctx, Some(filter)).await?.len(), 2);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_task_bmc_delete_many() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, events) = new_test_ctx().await?;
+		let project_id = create_test_project(ctx.clone(), "Project 01").await?;
+		let mut ids = Vec::new();
+		for title in ["Task 01", "Task 02", "Task 03"] {
+			ids.push(
+				TaskBmc::create(ctx.clone(), task_for_create(&project_id, title))
+					.await?
+					.id,
+			);
+		}
+		let comment_data = CommentForCreate {
+			task_id: TaskId::from_store(ids[0].clone()),
+			body: "Comment 01".to_string(),
+		};
+		CommentBmc::create(ctx.clone(), comment_data).await?;
+		let dep_data = TaskDepForCreate {
+			task_id: TaskId::from_store(ids[2].clone()),
+			blocker_id: TaskId::from_store(ids[1].clone()),
+		};
+		TaskDepBmc::create(ctx.clone(), dep_data).await?;
+		events.take("Model");
+
+		// --- EXEC
+		let target = TaskTarget::Ids(ids[..2].to_vec());
+		let result = TaskBmc::delete_many(ctx.clone(), target).await?;
+
+		// --- CHECK
+		assert_eq!(result.ids.len(), 2);
+		let evts = events.take("Model");
+		assert_eq!(evts.len(), 1, "no comment or dependency events");
+		assert_eq!(evts[0].label.as_deref(), Some("delete_many"));
+		assert!(CommentBmc::list(ctx.clone(), None).await?.is_empty());
+		assert!(TaskDepBmc::list(ctx.clone(), None).await?.is_empty());
+		assert_eq!(TaskBmc::list(ctx.clone(), None).await?.len(), 1);
+
+		// --- CHECK - ids of another table
+		let target = TaskTarget::Ids(vec![project_id.to_string()]);
+		let res = TaskBmc::delete_many(ctx.clone(), target).await;
+		assert!(matches!(res, Err(Error::StoreIdNotOfTable(_))));
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
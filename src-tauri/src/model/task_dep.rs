@@ -0,0 +1,306 @@
+//! All model and controller for the TaskDep type
+//!
+//! A TaskDep is a "blocks / blocked by" relation between two tasks,
+//! `task_id` being blocked by `blocker_id` (i.e., `blocker_id` blocks `task_id`).
+//!
+
+use super::bmc_base::{bmc_create, bmc_delete, bmc_list};
+use super::store::{Creatable, Filterable, Nanos};
+use super::validate::Validate;
+use super::{ModelMutateResultData, TaskBmc, TaskId};
+use crate::ctx::Ctx;
+use crate::{Error, Result};
+use modql::filter::{FilterNodes, OpValString, OpValsString};
+use modql::ListOptions;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+use ts_rs::TS;
+
+// region:    --- TaskDep
+
+#[derive(Serialize, Deserialize, TS, Debug, Clone)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct TaskDep {
+	pub id: String,
+	pub ctime: Nanos,
+	pub task_id: TaskId,
+	pub blocker_id: TaskId,
+}
+
+// endregion: --- TaskDep
+
+// region:    --- TaskDepForCreate
+
+#[derive(Serialize, Deserialize, TS, Debug)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct TaskDepForCreate {
+	pub task_id: TaskId,
+	pub blocker_id: TaskId,
+}
+
+impl Creatable for TaskDepForCreate {}
+
+impl Validate for TaskDepForCreate {}
+
+// endregion: --- TaskDepForCreate
+
+// region:    --- TaskDepFilter
+
+#[derive(FilterNodes, Deserialize, Debug)]
+pub struct TaskDepFilter {
+	pub task_id: Option<OpValsString>,
+	pub blocker_id: Option<OpValsString>,
+}
+
+impl Filterable for TaskDepFilter {}
+
+// endregion: --- TaskDepFilter
+
+// region:    --- TaskDepBmc
+
+pub struct TaskDepBmc;
+
+impl TaskDepBmc {
+	const ENTITY: &'static str = "task_dep";
+
+	/// Add a dependency, failing if it is a self dependency, a duplicate, or if it would create a cycle.
+	pub async fn create(ctx: Arc<Ctx>, data: TaskDepForCreate) -> Result<ModelMutateResultData> {
+		if data.task_id == data.blocker_id {
+			return Err(Error::TaskDepInvalid(format!(
+				"task {} cannot block itself",
+				data.task_id
+			)));
+		}
+
+		// make sure both tasks exist
+		TaskBmc::get(ctx.clone(), &data.task_id).await?;
+		TaskBmc::get(ctx.clone(), &data.blocker_id).await?;
+
+		let model_manager = ctx.get_model_manager();
+		let _task_dep_guard = model_manager.lock_task_deps().await;
+
+		let deps = Self::list(ctx.clone(), None).await?;
+		if deps
+			.iter()
+			.any(|d| d.task_id == data.task_id && d.blocker_id == data.blocker_id)
+		{
+			return Err(Error::TaskDepInvalid(format!(
+				"task {} is already blocked by {}",
+				data.task_id, data.blocker_id
+			)));
+		}
+		if let Some(cycle) = find_cycle(&deps, &data.task_id, &data.blocker_id) {
+			return Err(Error::TaskDepCycle(cycle));
+		}
+
+		bmc_create::<TaskDep, _>(ctx, Self::ENTITY, data).await
+	}
+
+	pub async fn delete(ctx: Arc<Ctx>, id: &str) -> Result<ModelMutateResultData> {
+		bmc_delete::<TaskDep>(ctx, Self::ENTITY, id).await
+	}
+
+	pub async fn list(ctx: Arc<Ctx>, filter: Option<TaskDepFilter>) -> Result<Vec<TaskDep>> {
+		bmc_list(ctx, Self::ENTITY, filter, ListOptions::default()).await
+	}
+
+	/// Returns the ids of the tasks blocking `task_id` which are not done yet.
+	pub async fn open_blockers(ctx: Arc<Ctx>, task_id: &str) -> Result<Vec<String>> {
+		let filter = TaskDepFilter {
+			task_id: Some(OpValString::Eq(task_id.to_string()).into()),
+			blocker_id: None,
+		};
+
+		let mut open_blocker_ids = Vec::new();
+		for dep in Self::list(ctx.clone(), Some(filter)).await? {
+			if !TaskBmc::get(ctx.clone(), &dep.blocker_id).await?.done {
+				open_blocker_ids.push(dep.blocker_id.into());
+			}
+		}
+
+		Ok(open_blocker_ids)
+	}
+
+	/// Delete all of the dependencies from or to a task (e.g., when the task gets deleted).
+	pub async fn delete_for_task(ctx: Arc<Ctx>, task_id: &str) -> Result<()> {
+		let deps = Self::list(ctx.clone(), None).await?;
+		for dep in deps
+			.into_iter()
+			.filter(|d| &*d.task_id == task_id || &*d.blocker_id == task_id)
+		{
+			Self::delete(ctx.clone(), &dep.id).await?;
+		}
+
+		Ok(())
+	}
+
+	/// Delete the dependencies of the tasks (as task or blocker), in one store statement, without their `Model` events
+	/// (i.e., for `TaskBmc::delete_many`, its event covering them).
+	pub(super) async fn delete_for_tasks(ctx: Arc<Ctx>, task_ids: &[String]) -> Result<()> {
+		let task_ids: BTreeSet<&str> = task_ids.iter().map(String::as_str).collect();
+		let ids: Vec<String> = Self::list(ctx.clone(), None)
+			.await?
+			.into_iter()
+			.filter(|d| task_ids.contains(&*d.task_id) || task_ids.contains(&*d.blocker_id))
+			.map(|d| d.id)
+			.collect();
+		ctx.get_model_manager()
+			.store(&ctx)
+			.exec_delete_many(Self::ENTITY, &ids)
+			.await?;
+
+		Ok(())
+	}
+}
+
+// endregion: --- TaskDepBmc
+
+// region:    --- Cycle Detection
+
+/// Returns the cycle path (starting and ending with `task_id`) if adding "`task_id` blocked by `blocker_id`"
+/// to `deps` would create a cycle, i.e., if `task_id` already (transitively) blocks `blocker_id`.
+fn find_cycle(deps: &[TaskDep], task_id: &str, blocker_id: &str) -> Option<Vec<String>> {
+	// task_id -> blocker_ids
+	let mut blocked_by: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+	for dep in deps {
+		blocked_by
+			.entry(&*dep.task_id)
+			.or_default()
+			.push(&*dep.blocker_id);
+	}
+
+	// Depth first walk of the blockers of `blocker_id`, keeping the current path.
+	let mut visited: BTreeSet<&str> = BTreeSet::new();
+	let mut path: Vec<&str> = vec![task_id, blocker_id];
+	let mut stack: Vec<std::slice::Iter<&str>> = Vec::new();
+	visited.insert(blocker_id);
+	stack.push(
+		blocked_by
+			.get(blocker_id)
+			.map(|v| v.as_slice())
+			.unwrap_or(&[])
+			.iter(),
+	);
+
+	while let Some(iter) = stack.last_mut() {
+		match iter.next() {
+			Some(&next) if next == task_id => {
+				path.push(next);
+				return Some(path.into_iter().map(String::from).collect());
+			}
+			Some(&next) => {
+				if visited.insert(next) {
+					path.push(next);
+					stack.push(
+						blocked_by
+							.get(next)
+							.map(|v| v.as_slice())
+							.unwrap_or(&[])
+							.iter(),
+					);
+				}
+			}
+			None => {
+				stack.pop();
+				path.pop();
+			}
+		}
+	}
+
+	None
+}
+
+// endregion: --- Cycle Detection
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::{find_cycle, Nanos, TaskDep, TaskDepBmc, TaskDepForCreate};
+	use crate::model::test_utils::{create_test_project, new_test_ctx, task_for_create};
+	use crate::model::{TaskBmc, TaskId};
+	use crate::Error;
+
+	fn deps(edges: &[(&str, &str)]) -> Vec<TaskDep> {
+		edges
+			.iter()
+			.enumerate()
+			.map(|(i, (task_id, blocker_id))| TaskDep {
+				id: format!("task_dep:{i}"),
+				ctime: Nanos(0),
+				task_id: TaskId::from_store(task_id.to_string()),
+				blocker_id: TaskId::from_store(blocker_id.to_string()),
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_find_cycle_none() -> anyhow::Result<()> {
+		// a blocked by b, b blocked by c, a blocked by d
+		let deps = deps(&[("a", "b"), ("b", "c"), ("a", "d")]);
+
+		assert_eq!(find_cycle(&deps, "a", "c"), None);
+		assert_eq!(find_cycle(&deps, "d", "c"), None);
+		assert_eq!(find_cycle(&[], "a", "b"), None);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_find_cycle_direct_and_transitive() -> anyhow::Result<()> {
+		let deps = deps(&[("a", "b"), ("b", "c"), ("c", "x"), ("b", "d"), ("d", "e")]);
+
+		assert_eq!(
+			find_cycle(&deps, "b", "a"),
+			Some(vec!["b".to_string(), "a".to_string(), "b".to_string()])
+		);
+		assert_eq!(
+			find_cycle(&deps, "e", "a"),
+			Some(
+				["e", "a", "b", "d", "e"]
+					.into_iter()
+					.map(String::from)
+					.collect()
+			)
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_task_dep_create_concurrent_cycle() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, _events) = new_test_ctx().await?;
+		let project_id = create_test_project(ctx.clone(), "Project 01").await?;
+		let task_a = TaskBmc::create(ctx.clone(), task_for_create(&project_id, "Task A"))
+			.await?
+			.id;
+		let task_b = TaskBmc::create(ctx.clone(), task_for_create(&project_id, "Task B"))
+			.await?
+			.id;
+		let dep = |task_id: &str, blocker_id: &str| TaskDepForCreate {
+			task_id: TaskId::from_store(task_id.to_string()),
+			blocker_id: TaskId::from_store(blocker_id.to_string()),
+		};
+
+		// --- EXEC
+		let (res_01, res_02) = tokio::join!(
+			TaskDepBmc::create(ctx.clone(), dep(&task_a, &task_b)),
+			TaskDepBmc::create(ctx.clone(), dep(&task_b, &task_a))
+		);
+
+		// --- CHECK
+		assert_eq!(
+			res_01.is_ok() as u8 + res_02.is_ok() as u8,
+			1,
+			"one dependency created"
+		);
+		assert!([res_01, res_02]
+			.into_iter()
+			.any(|res| matches!(res, Err(Error::TaskDepCycle(_)))));
+		assert_eq!(TaskDepBmc::list(ctx.clone(), None).await?.len(), 1);
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -0,0 +1,116 @@
+//! Utilities for the Backend Model Controller tests, with a headless `Ctx` (i.e., no Tauri app)
+//! on a new in-memory store, and the events collected.
+//!
+
+use super::{
+	EventLogWriter, ModelStore, ProjectBmc, ProjectForCreate, ProjectId, SubscriptionRefresher,
+	TaskForCreate,
+};
+use crate::ctx::Ctx;
+use crate::event::{EventBus, HubEvent, HubListener};
+use crate::Result;
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+
+/// Collects all of the published events (as the `TauriEmitter` would send them, i.e., with the resolved target).
+///
+/// Note: Also holds the temp app data dir of the `new_test_ctx` store, removed when the last clone
+///       (e.g., the one of the `EventBus` of the Ctx) is dropped.
+#[derive(Clone, Default)]
+pub struct EventCollector {
+	events: Arc<Mutex<Vec<HubEvent<Value>>>>,
+	_app_data_dir: Option<Arc<TempDir>>,
+}
+
+impl HubListener for EventCollector {
+	fn on_event(&self, ctx: &Arc<Ctx>, event: &HubEvent<Value>) {
+		let event = HubEvent {
+			target: event.target.resolve(ctx),
+			..event.clone()
+		};
+		self.events.lock().push(event);
+	}
+}
+
+impl EventCollector {
+	/// Take all of the events collected so far, and return the ones of the hub (e.g., `Model`).
+	pub fn take(&self, hub: &str) -> Vec<HubEvent<Value>> {
+		let events = std::mem::take(&mut *self.events.lock());
+		events.into_iter().filter(|evt| evt.hub == hub).collect()
+	}
+
+	/// Wait (up to 1s) for the events of the hub published by the async listeners (e.g., `Subscription`).
+	pub async fn wait_for(&self, hub: &str) -> Vec<HubEvent<Value>> {
+		for _ in 0..100 {
+			if self.events.lock().iter().any(|evt| evt.hub == hub) {
+				break;
+			}
+			tokio::time::sleep(Duration::from_millis(10)).await;
+		}
+		self.take(hub)
+	}
+}
+
+/// New Ctx on an empty store, with the listeners of the app (the `EventCollector` for the frontend).
+///
+/// Note: The `Model` events are not coalesced (i.e., emitted right away), see `new_test_ctx_with_window`.
+pub async fn new_test_ctx() -> Result<(Arc<Ctx>, EventCollector)> {
+	new_test_ctx_with_window(Duration::ZERO).await
+}
+
+/// New Ctx (see `new_test_ctx`) with the window of the `Model` event coalescing.
+pub async fn new_test_ctx_with_window(window: Duration) -> Result<(Arc<Ctx>, EventCollector)> {
+	// Note: Own app data dir, as the blob gc of a store would delete the blobs of the other ones (same db name).
+	let app_data_dir = tempfile::tempdir()?;
+	let model_manager = ModelStore::new(app_data_dir.path(), "memory")
+		.await?
+		.with_event_window(window);
+	let model_manager = Arc::new(model_manager);
+
+	let events = EventCollector {
+		events: Arc::default(),
+		_app_data_dir: Some(Arc::new(app_data_dir)),
+	};
+	let event_bus = EventBus::default()
+		.with_listener(events.clone())
+		.with_async_listener(SubscriptionRefresher)
+		.with_async_listener(EventLogWriter);
+
+	Ok((
+		Arc::new(Ctx::new(model_manager, Arc::new(event_bus))),
+		events,
+	))
+}
+
+/// The data of a new project, with only its name.
+pub fn project_for_create(name: &str) -> ProjectForCreate {
+	ProjectForCreate {
+		name: name.to_string(),
+		description: None,
+		color: None,
+		icon: None,
+		archived: None,
+		template: None,
+	}
+}
+
+/// The data of a new task of the project, with only its title.
+pub fn task_for_create(project_id: &ProjectId, title: &str) -> TaskForCreate {
+	TaskForCreate {
+		project_id: project_id.clone(),
+		title: title.to_string(),
+		done: None,
+		desc: None,
+		due_date: None,
+		recur_rule: None,
+	}
+}
+
+/// Create a project (see `project_for_create`), and return its id.
+pub async fn create_test_project(ctx: Arc<Ctx>, name: &str) -> Result<ProjectId> {
+	let id = ProjectBmc::create(ctx, project_for_create(name)).await?.id;
+	Ok(ProjectId::from_store(id))
+}
@@ -0,0 +1,436 @@
+//! All model and controller for the TimeEntry type, and the time report.
+//!
+//! A TimeEntry is created by `start_timer` and completed by `stop_timer`.
+//! At most one timer (i.e., TimeEntry without `stop`) can be running at a time.
+//!
+//! Notes:
+//!   - `start`/`stop` are stored as epoch nanoseconds (like `ctime`), and serialized as strings.
+//!   - The `project_id` of the task is copied on the entry on start, to report per project without loading the tasks.
+//!
+
+use super::bmc_base::{bmc_create, bmc_delete, bmc_list, bmc_update};
+use super::recur::parse_date;
+use super::store::{Creatable, Filterable, Nanos, Patchable};
+use super::validate::{Validate, Violations, TEXT_RULE};
+use super::{ModelMutateResultData, ProjectId, TaskBmc, TaskId};
+use crate::ctx::Ctx;
+use crate::{Error, Result};
+use chrono::{Duration, Local, NaiveDate, TimeZone};
+use modql::filter::{FilterNodes, OpValBool, OpValString, OpValsBool, OpValsString};
+use modql::ListOptions;
+use serde::{Deserialize, Serialize};
+use serde_with_macros::skip_serializing_none;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use surrealdb::sql::Datetime;
+use ts_rs::TS;
+
+const NANOS_PER_SEC: i64 = 1_000_000_000;
+
+// region:    --- TimeEntry
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, TS, Debug, Clone)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct TimeEntry {
+	pub id: String,
+	pub ctime: Nanos,
+	pub task_id: TaskId,
+	pub project_id: ProjectId,
+
+	pub start: Nanos,
+	/// None while the timer is running
+	pub stop: Option<Nanos>,
+	/// In seconds, set when stopped
+	#[ts(type = "number")]
+	pub duration: Option<u64>,
+	pub note: Option<String>,
+}
+
+// endregion: --- TimeEntry
+
+// region:    --- TimerForStart
+
+#[derive(Deserialize, TS, Debug)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct TimerForStart {
+	pub task_id: TaskId,
+	pub note: Option<String>,
+}
+
+/// The running entry as stored on start.
+#[derive(Serialize)]
+struct TimeEntryForStart {
+	task_id: TaskId,
+	project_id: ProjectId,
+	note: Option<String>,
+	start: i64,
+	running: bool,
+}
+
+impl Creatable for TimeEntryForStart {}
+
+impl Validate for TimeEntryForStart {
+	fn check(&self, violations: &mut Violations) {
+		violations.opt_text("note", self.note.as_deref(), TEXT_RULE);
+	}
+}
+
+// endregion: --- TimerForStart
+
+// region:    --- TimeEntryForStop
+
+#[derive(Serialize)]
+struct TimeEntryForStop {
+	stop: i64,
+	duration: i64,
+	running: bool,
+}
+
+impl Patchable for TimeEntryForStop {}
+
+impl Validate for TimeEntryForStop {}
+
+// endregion: --- TimeEntryForStop
+
+// region:    --- TimeEntryForUpdate
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, TS, Debug)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct TimeEntryForUpdate {
+	pub note: Option<String>,
+}
+
+impl Patchable for TimeEntryForUpdate {}
+
+impl Validate for TimeEntryForUpdate {
+	fn check(&self, violations: &mut Violations) {
+		violations.opt_text("note", self.note.as_deref(), TEXT_RULE);
+	}
+}
+
+// endregion: --- TimeEntryForUpdate
+
+// region:    --- TimeEntryFilter
+
+#[derive(FilterNodes, Deserialize, Debug)]
+pub struct TimeEntryFilter {
+	pub task_id: Option<OpValsString>,
+	pub project_id: Option<OpValsString>,
+	pub running: Option<OpValsBool>,
+}
+
+impl Filterable for TimeEntryFilter {}
+
+// endregion: --- TimeEntryFilter
+
+// region:    --- TimeReport
+
+/// Date range (inclusive, local time) of the report, as `YYYY-MM-DD`.
+#[derive(Deserialize, TS, Debug)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct TimeReportQuery {
+	pub from: String,
+	pub to: String,
+	pub project_id: Option<String>,
+}
+
+#[derive(Serialize, TS, Debug, PartialEq)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct TimeTotal {
+	/// task_id or project_id
+	pub id: String,
+	/// In seconds
+	#[ts(type = "number")]
+	pub total: u64,
+}
+
+#[derive(Serialize, TS, Debug)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct TimeReport {
+	pub from: String,
+	pub to: String,
+	pub tasks: Vec<TimeTotal>,
+	pub projects: Vec<TimeTotal>,
+	/// In seconds
+	#[ts(type = "number")]
+	pub total: u64,
+}
+
+// endregion: --- TimeReport
+
+// region:    --- TimeEntryBmc
+
+pub struct TimeEntryBmc;
+
+impl TimeEntryBmc {
+	const ENTITY: &'static str = "time_entry";
+
+	pub async fn update(
+		ctx: Arc<Ctx>,
+		id: &str,
+		data: TimeEntryForUpdate,
+	) -> Result<ModelMutateResultData> {
+		bmc_update::<TimeEntry, _>(ctx, Self::ENTITY, id, data).await
+	}
+
+	pub async fn delete(ctx: Arc<Ctx>, id: &str) -> Result<ModelMutateResultData> {
+		bmc_delete::<TimeEntry>(ctx, Self::ENTITY, id).await
+	}
+
+	pub async fn list(ctx: Arc<Ctx>, filter: Option<TimeEntryFilter>) -> Result<Vec<TimeEntry>> {
+		let opts = ListOptions {
+			limit: None,
+			offset: None,
+			order_bys: Some("!start".into()),
+		};
+		bmc_list(ctx, Self::ENTITY, filter, opts).await
+	}
+
+	/// Returns the running time entry, if any.
+	pub async fn running(ctx: Arc<Ctx>) -> Result<Option<TimeEntry>> {
+		let filter = TimeEntryFilter {
+			task_id: None,
+			project_id: None,
+			running: Some(OpValBool::Eq(true).into()),
+		};
+		Ok(Self::list(ctx, Some(filter)).await?.into_iter().next())
+	}
+
+	/// Start a timer on a task. Fails if a timer is already running, or if the task is read-only (archived project).
+	pub async fn start_timer(ctx: Arc<Ctx>, data: TimerForStart) -> Result<ModelMutateResultData> {
+		let model_manager = ctx.get_model_manager();
+		let _timer_guard = model_manager.lock_timer().await;
+
+		if let Some(running) = Self::running(ctx.clone()).await? {
+			return Err(Error::TimerAlreadyRunning(running.id));
+		}
+
+		let task = TaskBmc::get_writable(ctx.clone(), &data.task_id).await?;
+
+		let data = TimeEntryForStart {
+			task_id: task.id,
+			project_id: task.project_id,
+			note: data.note,
+			start: now_nanos(),
+			running: true,
+		};
+		bmc_create::<TimeEntry, _>(ctx, Self::ENTITY, data).await
+	}
+
+	/// Stop the running timer. Fails if no timer is running.
+	pub async fn stop_timer(ctx: Arc<Ctx>) -> Result<ModelMutateResultData> {
+		let model_manager = ctx.get_model_manager();
+		let _timer_guard = model_manager.lock_timer().await;
+
+		let running = Self::running(ctx.clone())
+			.await?
+			.ok_or(Error::TimerNotRunning)?;
+
+		let start = running.start.0;
+		let stop = now_nanos().max(start);
+		let data = TimeEntryForStop {
+			stop,
+			duration: (stop - start) / NANOS_PER_SEC,
+			running: false,
+		};
+		bmc_update::<TimeEntry, _>(ctx, Self::ENTITY, &running.id, data).await
+	}
+
+	/// Totals per task and per project of the time tracked within the date range.
+	/// Entries overlapping the range boundaries are clipped, and the running timer counts until now.
+	pub async fn report(ctx: Arc<Ctx>, query: TimeReportQuery) -> Result<TimeReport> {
+		let range_start = local_day_start_nanos(parse_date(&query.from)?)?;
+		let range_end = local_day_start_nanos(parse_date(&query.to)? + Duration::days(1))?;
+
+		let filter = query.project_id.map(|project_id| TimeEntryFilter {
+			task_id: None,
+			project_id: Some(OpValString::Eq(project_id).into()),
+			running: None,
+		});
+		let entries = Self::list(ctx, filter).await?;
+
+		let (tasks, projects) = aggregate(&entries, range_start, range_end, now_nanos());
+
+		let to_totals = |map: BTreeMap<String, u64>| -> Vec<TimeTotal> {
+			map.into_iter()
+				.map(|(id, total)| TimeTotal { id, total })
+				.collect()
+		};
+
+		Ok(TimeReport {
+			from: query.from,
+			to: query.to,
+			total: projects.values().sum(),
+			tasks: to_totals(tasks),
+			projects: to_totals(projects),
+		})
+	}
+
+	/// Delete all of the time entries of a task (e.g., when the task gets deleted).
+	pub async fn delete_for_task(ctx: Arc<Ctx>, task_id: &str) -> Result<()> {
+		let filter = TimeEntryFilter {
+			task_id: Some(OpValString::Eq(task_id.to_string()).into()),
+			project_id: None,
+			running: None,
+		};
+		for time_entry in Self::list(ctx.clone(), Some(filter)).await? {
+			Self::delete(ctx.clone(), &time_entry.id).await?;
+		}
+
+		Ok(())
+	}
+
+	/// Delete the time entries of the tasks, in one store statement, without their `Model` events
+	/// (i.e., for `TaskBmc::delete_many`, its event covering them).
+	pub(super) async fn delete_for_tasks(ctx: Arc<Ctx>, task_ids: &[String]) -> Result<()> {
+		let filter = TimeEntryFilter {
+			task_id: Some(OpValString::In(task_ids.to_vec()).into()),
+			project_id: None,
+			running: None,
+		};
+		let ids: Vec<String> = Self::list(ctx.clone(), Some(filter))
+			.await?
+			.into_iter()
+			.map(|e| e.id)
+			.collect();
+		ctx.get_model_manager()
+			.store(&ctx)
+			.exec_delete_many(Self::ENTITY, &ids)
+			.await?;
+
+		Ok(())
+	}
+}
+
+// endregion: --- TimeEntryBmc
+
+// region:    --- Time Utils
+
+fn now_nanos() -> i64 {
+	Datetime::default().timestamp_nanos()
+}
+
+fn local_day_start_nanos(date: NaiveDate) -> Result<i64> {
+	date.and_hms_opt(0, 0, 0)
+		.and_then(|dt| Local.from_local_datetime(&dt).earliest())
+		.map(|dt| dt.timestamp_nanos())
+		.ok_or_else(|| Error::DateInvalid(date.to_string()))
+}
+
+/// Returns the (per task_id, per project_id) totals, in seconds, of the entries time within `[range_start, range_end)`.
+/// Running entries (without stop) count until `now`.
+fn aggregate(
+	entries: &[TimeEntry],
+	range_start: i64,
+	range_end: i64,
+	now: i64,
+) -> (BTreeMap<String, u64>, BTreeMap<String, u64>) {
+	let mut tasks: BTreeMap<String, u64> = BTreeMap::new();
+	let mut projects: BTreeMap<String, u64> = BTreeMap::new();
+
+	for entry in entries {
+		let start = entry.start.0;
+		let stop = entry.stop.map(|stop| stop.0).unwrap_or(now);
+
+		let overlap = stop.min(range_end) - start.max(range_start);
+		if overlap <= 0 {
+			continue;
+		}
+		let secs = (overlap / NANOS_PER_SEC) as u64;
+
+		*tasks.entry(entry.task_id.to_string()).or_default() += secs;
+		*projects.entry(entry.project_id.to_string()).or_default() += secs;
+	}
+
+	(tasks, projects)
+}
+
+// endregion: --- Time Utils
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::{aggregate, Nanos, TimeEntry, TimeEntryBmc, TimerForStart, NANOS_PER_SEC};
+	use crate::model::test_utils::{create_test_project, new_test_ctx, task_for_create};
+	use crate::model::{ProjectId, TaskBmc, TaskId};
+	use crate::Error;
+
+	fn entry(task_id: &str, project_id: &str, start: i64, stop: Option<i64>) -> TimeEntry {
+		TimeEntry {
+			id: format!("time_entry:{task_id}{start}"),
+			ctime: Nanos(0),
+			task_id: TaskId::from_store(task_id.to_string()),
+			project_id: ProjectId::from_store(project_id.to_string()),
+			start: Nanos(start * NANOS_PER_SEC),
+			stop: stop.map(|s| Nanos(s * NANOS_PER_SEC)),
+			duration: stop.map(|s| (s - start) as u64),
+			note: None,
+		}
+	}
+
+	#[test]
+	fn test_time_aggregate() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let entries = vec![
+			entry("task:1", "project:a", 100, Some(200)), // inside: 100
+			entry("task:1", "project:a", 50, Some(150)),  // clipped at start: 50
+			entry("task:2", "project:a", 950, Some(1100)), // clipped at end: 50
+			entry("task:3", "project:b", 0, Some(90)),    // outside
+			entry("task:3", "project:b", 900, None),      // running until now: 60
+		];
+
+		// --- EXEC
+		let (tasks, projects) = aggregate(
+			&entries,
+			100 * NANOS_PER_SEC,
+			1000 * NANOS_PER_SEC,
+			960 * NANOS_PER_SEC,
+		);
+
+		// --- CHECK
+		assert_eq!(tasks.get("task:1"), Some(&150));
+		assert_eq!(tasks.get("task:2"), Some(&50));
+		assert_eq!(tasks.get("task:3"), Some(&60));
+		assert_eq!(projects.get("project:a"), Some(&200));
+		assert_eq!(projects.get("project:b"), Some(&60));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_time_start_timer_concurrent() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, _events) = new_test_ctx().await?;
+		let project_id = create_test_project(ctx.clone(), "Project 01").await?;
+		let task_id = TaskBmc::create(ctx.clone(), task_for_create(&project_id, "Task 01"))
+			.await?
+			.id;
+		let data = || TimerForStart {
+			task_id: TaskId::from_store(task_id.clone()),
+			note: None,
+		};
+
+		// --- EXEC
+		let (res_01, res_02) = tokio::join!(
+			TimeEntryBmc::start_timer(ctx.clone(), data()),
+			TimeEntryBmc::start_timer(ctx.clone(), data())
+		);
+
+		// --- CHECK
+		assert_eq!(
+			res_01.is_ok() as u8 + res_02.is_ok() as u8,
+			1,
+			"one timer started"
+		);
+		assert!([res_01, res_02]
+			.into_iter()
+			.any(|res| matches!(res, Err(Error::TimerAlreadyRunning(_)))));
+		let entries = TimeEntryBmc::list(ctx.clone(), None).await?;
+		assert_eq!(entries.len(), 1);
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
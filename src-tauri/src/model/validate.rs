@@ -0,0 +1,214 @@
+//! Validation of the data sent to the Backend Model Controllers (e.g., `ProjectForCreate`).
+//!
+//! The `Validate` trait is invoked by `bmc_create` and `bmc_update`, and reports all of the violations at once
+//! (as `Error::Validation`), so the UI can show a message per field.
+//!
+//! Notes:
+//!   - The text rules apply to the trimmed value (i.e., a blank name is a missing name).
+//!   - Types without rules just implement the trait with the default `check` (e.g., `impl Validate for X {}`).
+
+use crate::{Error, Result};
+use serde::Serialize;
+use ts_rs::TS;
+
+// region:    --- Validate
+
+pub trait Validate {
+	/// Add the violations of the data (no rules by default).
+	fn check(&self, _violations: &mut Violations) {}
+
+	/// Fails with `Error::Validation` (with all of the violations) if the data is not valid.
+	fn validate(&self) -> Result<()> {
+		let mut violations = Violations::default();
+		self.check(&mut violations);
+		violations.into_result()
+	}
+}
+
+#[derive(Serialize, TS, Debug, Clone, PartialEq, Eq)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct FieldViolation {
+	pub field: String,
+	pub message: String,
+}
+
+// endregion: --- Validate
+
+// region:    --- Rules
+
+/// Bounds (in characters, of the trimmed value) and allowed characters of a text property.
+pub struct TextRule {
+	pub min: usize,
+	pub max: usize,
+	/// Allow new lines and tabs
+	pub multiline: bool,
+}
+
+pub const NAME_RULE: TextRule = TextRule {
+	min: 1,
+	max: 128,
+	multiline: false,
+};
+
+pub const TITLE_RULE: TextRule = TextRule {
+	min: 1,
+	max: 256,
+	multiline: false,
+};
+
+pub const TEXT_RULE: TextRule = TextRule {
+	min: 0,
+	max: 10_000,
+	multiline: true,
+};
+
+pub const BODY_RULE: TextRule = TextRule {
+	min: 1,
+	max: 10_000,
+	multiline: true,
+};
+
+#[derive(Default, Debug)]
+pub struct Violations(Vec<FieldViolation>);
+
+impl Violations {
+	pub fn add(&mut self, field: &str, message: impl Into<String>) {
+		self.0.push(FieldViolation {
+			field: field.to_string(),
+			message: message.into(),
+		});
+	}
+
+	pub fn text(&mut self, field: &str, val: &str, rule: TextRule) {
+		let val = val.trim();
+		let len = val.chars().count();
+
+		if len == 0 && rule.min > 0 {
+			self.add(field, "is required");
+		} else if len < rule.min {
+			self.add(field, format!("must have at least {} characters", rule.min));
+		} else if len > rule.max {
+			self.add(field, format!("must have at most {} characters", rule.max));
+		}
+
+		let allowed =
+			|c: char| !c.is_control() || (rule.multiline && matches!(c, '\n' | '\r' | '\t'));
+		if !val.chars().all(allowed) {
+			let message = if rule.multiline {
+				"must not contain control characters"
+			} else {
+				"must be a single line, without control characters"
+			};
+			self.add(field, message);
+		}
+	}
+
+	pub fn opt_text(&mut self, field: &str, val: Option<&str>, rule: TextRule) {
+		if let Some(val) = val {
+			self.text(field, val, rule);
+		}
+	}
+
+	/// CSS hex color (`#rgb` or `#rrggbb`)
+	pub fn opt_color(&mut self, field: &str, val: Option<&str>) {
+		if let Some(val) = val {
+			let valid = match val.strip_prefix('#') {
+				Some(hex) => {
+					matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit())
+				}
+				None => false,
+			};
+			if !valid {
+				self.add(field, "must be a hex color (e.g., #3b82f6)");
+			}
+		}
+	}
+
+	/// Key (e.g., icon name) of lowercase ascii letters, digits, and `-` (at most 64 characters)
+	pub fn opt_key(&mut self, field: &str, val: Option<&str>) {
+		if let Some(val) = val {
+			let valid = !val.is_empty()
+				&& val.len() <= 64
+				&& val
+					.chars()
+					.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+			if !valid {
+				self.add(
+					field,
+					"must be lowercase letters, digits, and '-' (at most 64)",
+				);
+			}
+		}
+	}
+
+	fn into_result(self) -> Result<()> {
+		if self.0.is_empty() {
+			Ok(())
+		} else {
+			Err(Error::Validation(self.0))
+		}
+	}
+}
+
+// endregion: --- Rules
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::{Validate, Violations, NAME_RULE, TEXT_RULE};
+	use crate::Error;
+
+	struct TestData {
+		name: String,
+		desc: Option<String>,
+		color: Option<String>,
+	}
+
+	impl Validate for TestData {
+		fn check(&self, violations: &mut Violations) {
+			violations.text("name", &self.name, NAME_RULE);
+			violations.opt_text("desc", self.desc.as_deref(), TEXT_RULE);
+			violations.opt_color("color", self.color.as_deref());
+		}
+	}
+
+	#[test]
+	fn test_validate_all_violations() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let valid = TestData {
+			name: " Project A ".to_string(),
+			desc: Some("line 1\nline 2".to_string()),
+			color: Some("#3b82f6".to_string()),
+		};
+		let invalid = TestData {
+			name: "   ".to_string(),
+			desc: Some("x".repeat(10_001)),
+			color: Some("blue".to_string()),
+		};
+		let invalid_chars = TestData {
+			name: "Project\nA".to_string(),
+			desc: None,
+			color: None,
+		};
+
+		// --- EXEC & CHECK
+		assert!(valid.validate().is_ok());
+
+		match invalid.validate() {
+			Err(Error::Validation(violations)) => {
+				let fields: Vec<&str> = violations.iter().map(|v| v.field.as_str()).collect();
+				assert_eq!(fields, vec!["name", "desc", "color"]);
+				assert_eq!(violations[0].message, "is required");
+			}
+			other => panic!("should be a validation error, got {other:?}"),
+		}
+
+		match invalid_chars.validate() {
+			Err(Error::Validation(violations)) => assert_eq!(violations.len(), 1),
+			other => panic!("should be a validation error, got {other:?}"),
+		}
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -0,0 +1,351 @@
+//! All model and controller for the Workspace type
+//!
+//! A workspace is a SurrealDB database (of the app namespace) holding its own projects, tasks, ...
+//! The workspace records themselves are stored in a dedicated "meta" database (see `ModelStore::meta_store`),
+//! and `switch_workspace` swaps the database of the ModelStore store at runtime.
+//!
+//! Notes:
+//!   - Workspace mutations fire `Workspace` hub events (topic `workspace`, label the action),
+//!     the frontend should reload its data on `switch`.
+//!   - Each workspace has its own attachment blob directory (see `ModelStore::blob_store`).
+//!
+
+use super::store::{from_object, Creatable, Nanos, Patchable, SurrealStore, DEFAULT_DB};
+use super::validate::{Validate, Violations, NAME_RULE};
+use super::ModelMutateResultData;
+use crate::ctx::Ctx;
+use crate::event::{EventTarget, HubEvent};
+use crate::{Error, Result};
+use modql::filter::FilterNode;
+use modql::ListOptions;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use surrealdb::sql::Datetime;
+use ts_rs::TS;
+
+const ENTITY: &str = "workspace";
+
+// region:    --- Workspace
+
+#[derive(Serialize, Deserialize, TS, Debug, Clone)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct Workspace {
+	pub id: String,
+	pub ctime: Nanos,
+	pub name: String,
+	/// Database name of the workspace
+	pub db: String,
+	/// If this is the current workspace (set by WorkspaceBmc)
+	#[serde(default)]
+	pub active: bool,
+}
+
+// endregion: --- Workspace
+
+// region:    --- WorkspaceForCreate
+
+#[derive(Deserialize, TS, Debug)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct WorkspaceForCreate {
+	pub name: String,
+}
+
+/// The workspace record as stored.
+#[derive(Serialize)]
+struct WorkspaceData {
+	name: String,
+	db: String,
+}
+
+impl Creatable for WorkspaceData {}
+
+impl Validate for WorkspaceForCreate {
+	fn check(&self, violations: &mut Violations) {
+		violations.text("name", &self.name, NAME_RULE);
+	}
+}
+
+// endregion: --- WorkspaceForCreate
+
+// region:    --- WorkspaceForUpdate
+
+#[derive(Serialize, Deserialize, TS, Debug)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct WorkspaceForUpdate {
+	pub name: Option<String>,
+}
+
+impl Patchable for WorkspaceForUpdate {}
+
+impl Validate for WorkspaceForUpdate {
+	fn check(&self, violations: &mut Violations) {
+		violations.opt_text("name", self.name.as_deref(), NAME_RULE);
+	}
+}
+
+// endregion: --- WorkspaceForUpdate
+
+// region:    --- WorkspaceBmc
+
+pub struct WorkspaceBmc;
+
+impl WorkspaceBmc {
+	pub async fn get(ctx: Arc<Ctx>, id: &str) -> Result<Workspace> {
+		let model_manager = ctx.get_model_manager();
+		let workspace: Workspace =
+			from_object(model_manager.meta_store().exec_get(ENTITY, id).await?)?;
+		Ok(with_active(workspace, &model_manager.active_db()))
+	}
+
+	pub async fn list(ctx: Arc<Ctx>) -> Result<Vec<Workspace>> {
+		let model_manager = ctx.get_model_manager();
+		let active_db = model_manager.active_db();
+		let opts = ListOptions {
+			order_bys: Some("ctime".into()),
+			..Default::default()
+		};
+
+		model_manager
+			.meta_store()
+			.exec_select::<FilterNode>(ENTITY, None, opts)
+			.await?
+			.into_iter()
+			.map(|o| from_object(o).map(|w| with_active(w, &active_db)))
+			.collect()
+	}
+
+	/// Create a workspace and its (empty) database. Does not switch to it.
+	pub async fn create(ctx: Arc<Ctx>, data: WorkspaceForCreate) -> Result<ModelMutateResultData> {
+		data.validate()?;
+		let data = WorkspaceData {
+			name: data.name,
+			db: format!("ws_{}", Datetime::default().timestamp_nanos()),
+		};
+		let id = ctx
+			.get_model_manager()
+			.meta_store()
+			.exec_create(ENTITY, data)
+			.await?;
+
+		let result_data = ModelMutateResultData::from(id);
+		fire_workspace_event(&ctx, "create", result_data.clone());
+
+		Ok(result_data)
+	}
+
+	/// Update (i.e., rename) a workspace.
+	pub async fn update(
+		ctx: Arc<Ctx>,
+		id: &str,
+		data: WorkspaceForUpdate,
+	) -> Result<ModelMutateResultData> {
+		data.validate()?;
+		let id = ctx
+			.get_model_manager()
+			.meta_store()
+			.exec_merge(ENTITY, id, data)
+			.await?;
+
+		let result_data = ModelMutateResultData::from(id);
+		fire_workspace_event(&ctx, "update", result_data.clone());
+
+		Ok(result_data)
+	}
+
+	/// Delete a workspace with its database and attachment files. The active workspace cannot be deleted.
+	///
+	/// Note: The database is removed before the workspace record, so a failed remove can be retried.
+	pub async fn delete(ctx: Arc<Ctx>, id: &str) -> Result<ModelMutateResultData> {
+		let model_manager = ctx.get_model_manager();
+		let _workspace_guard = model_manager.lock_workspaces().await;
+
+		let workspace = Self::get(ctx.clone(), id).await?;
+		if workspace.active {
+			return Err(Error::WorkspaceActive(workspace.id));
+		}
+
+		model_manager
+			.active_store()
+			.exec_remove_db(&workspace.db)
+			.await?;
+		model_manager.meta_store().exec_delete(ENTITY, id).await?;
+
+		let blob_dir = model_manager.blob_dir(&workspace.db);
+		if blob_dir.exists() {
+			tokio::fs::remove_dir_all(blob_dir).await?;
+		}
+
+		let result_data = ModelMutateResultData::from(workspace.id);
+		fire_workspace_event(&ctx, "delete", result_data.clone());
+
+		Ok(result_data)
+	}
+
+	/// Make a workspace the active one. Fires a `Workspace` / `workspace` / `switch` event with the workspace.
+	pub async fn switch(ctx: Arc<Ctx>, id: &str) -> Result<Workspace> {
+		let model_manager = ctx.get_model_manager();
+		let _workspace_guard = model_manager.lock_workspaces().await;
+
+		let workspace = Self::get(ctx.clone(), id).await?;
+		model_manager.active_store().set_db(&workspace.db)?;
+		model_manager.subscriptions().clear().await;
+		let workspace = with_active(workspace.clone(), &workspace.db);
+
+		fire_workspace_event(&ctx, "switch", workspace.clone());
+
+		Ok(workspace)
+	}
+}
+
+// endregion: --- WorkspaceBmc
+
+// region:    --- Workspace Utils
+
+/// Register the default workspace (i.e., the default store database) if not already registered.
+pub(super) async fn init_workspaces(meta_store: &SurrealStore) -> Result<()> {
+	let default_filter = FilterNode::from(("db", DEFAULT_DB));
+	let existing = meta_store
+		.exec_select(ENTITY, Some(default_filter), ListOptions::default())
+		.await?;
+
+	if existing.is_empty() {
+		let data = WorkspaceData {
+			name: "Default".to_string(),
+			db: DEFAULT_DB.to_string(),
+		};
+		meta_store.exec_create(ENTITY, data).await?;
+	}
+
+	Ok(())
+}
+
+fn with_active(mut workspace: Workspace, active_db: &str) -> Workspace {
+	workspace.active = workspace.db == active_db;
+	workspace
+}
+
+fn fire_workspace_event<D>(ctx: &Arc<Ctx>, action: &str, data: D)
+where
+	D: Serialize + Clone,
+{
+	ctx.emit_hub_event(HubEvent {
+		hub: "Workspace".to_string(),
+		topic: ENTITY.to_string(),
+		label: Some(action.to_string()),
+		seq: None,
+		request_id: ctx.request_id().map(String::from),
+		data: Some(data),
+		target: EventTarget::All,
+	});
+}
+
+// endregion: --- Workspace Utils
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::event::EventBus;
+	use crate::model::test_utils::{new_test_ctx, project_for_create};
+	use crate::model::ProjectBmc;
+
+	#[tokio::test]
+	async fn test_workspace_switch_during_call() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, _events) = new_test_ctx().await?;
+		let data = WorkspaceForCreate {
+			name: "Workspace 02".to_string(),
+		};
+		let workspace_id = WorkspaceBmc::create(ctx.clone(), data).await?.id;
+
+		// --- EXEC - switch, with a call (i.e., its ctx) started before
+		let workspace = WorkspaceBmc::switch(ctx.clone(), &workspace_id).await?;
+		ProjectBmc::create(ctx.clone(), project_for_create("Project 01")).await?;
+
+		// --- CHECK - the call stays on its workspace
+		assert_eq!(ctx.workspace_db(), DEFAULT_DB);
+		assert_eq!(ProjectBmc::list(ctx.clone(), None).await?.len(), 1);
+		let new_ctx = Arc::new(Ctx::new(
+			ctx.get_model_manager(),
+			Arc::new(EventBus::default()),
+		));
+		assert_eq!(new_ctx.workspace_db(), workspace.db);
+		assert!(ProjectBmc::list(new_ctx, None).await?.is_empty());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_workspace_delete() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, _events) = new_test_ctx().await?;
+		let data = |name: &str| WorkspaceForCreate {
+			name: name.to_string(),
+		};
+		let empty_id = WorkspaceBmc::create(ctx.clone(), data("Workspace 02"))
+			.await?
+			.id;
+		let used_id = WorkspaceBmc::create(ctx.clone(), data("Workspace 03"))
+			.await?
+			.id;
+		let used_ws = WorkspaceBmc::switch(ctx.clone(), &used_id).await?;
+		let used_ctx = Arc::new(Ctx::new(
+			ctx.get_model_manager(),
+			Arc::new(EventBus::default()),
+		));
+		ProjectBmc::create(used_ctx, project_for_create("Project 01")).await?;
+		let default_id = WorkspaceBmc::list(ctx.clone())
+			.await?
+			.into_iter()
+			.find(|w| w.db == DEFAULT_DB)
+			.map(|w| w.id)
+			.unwrap();
+		WorkspaceBmc::switch(ctx.clone(), &default_id).await?;
+
+		// --- EXEC
+		WorkspaceBmc::delete(ctx.clone(), &empty_id).await?;
+		WorkspaceBmc::delete(ctx.clone(), &used_id).await?;
+
+		// --- CHECK
+		let workspaces = WorkspaceBmc::list(ctx.clone()).await?;
+		assert_eq!(workspaces.len(), 1);
+		assert_eq!(workspaces[0].db, DEFAULT_DB);
+		let res = WorkspaceBmc::delete(ctx.clone(), &default_id).await;
+		assert!(matches!(res, Err(Error::WorkspaceActive(_))));
+		let removed_store = ctx.get_model_manager().active_store().on_db(&used_ws.db);
+		assert!(
+			removed_store
+				.exec_select::<FilterNode>("project", None, ListOptions::default())
+				.await
+				.map(|projects| projects.is_empty())
+				.unwrap_or(true),
+			"database removed"
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_workspace_delete_while_switching() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let (ctx, _events) = new_test_ctx().await?;
+		let data = WorkspaceForCreate {
+			name: "Workspace 02".to_string(),
+		};
+		let workspace_id = WorkspaceBmc::create(ctx.clone(), data).await?.id;
+
+		// --- EXEC
+		let (switched, deleted) = tokio::join!(
+			WorkspaceBmc::switch(ctx.clone(), &workspace_id),
+			WorkspaceBmc::delete(ctx.clone(), &workspace_id)
+		);
+
+		// --- CHECK - not both, i.e., never active once deleted
+		assert_eq!(switched.is_ok() as u8 + deleted.is_ok() as u8, 1);
+		let workspaces = WorkspaceBmc::list(ctx.clone()).await?;
+		assert_eq!(workspaces.iter().filter(|w| w.active).count(), 1);
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -1,11 +1,6 @@
 //! Application wide utilities. Most will be re-exported.
 //!
 
-mod x_take;
-
-// --- re-exports
-pub use self::x_take::*;
-
 // from: https://github.com/surrealdb/surrealdb.wasm/blob/main/src/mac/mod.rs
 macro_rules! map {
     ($($k:expr => $v:expr),* $(,)?) => {{
@@ -0,0 +1,142 @@
+//! Project windows, managed by the backend (i.e., one window per project, opened or focused by `open_project_window`).
+//!
+//! Notes:
+//!   - The project window loads the app with the `project_id` url param (i.e., `index.html?project_id=..`).
+//!   - The `ProjectWindows` registry (Tauri state) tells the `TauriEmitter` which windows are of which project,
+//!     for the `EventTarget::Project` events.
+//!
+
+use crate::ctx::Ctx;
+use crate::model::{ProjectBmc, ProjectId};
+use crate::prelude::*;
+use crate::{Error, Result};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, Window, WindowBuilder, WindowEvent, WindowUrl, Wry};
+use ts_rs::TS;
+
+// region:    --- ProjectWindows
+
+/// The project of each project window (by window label).
+#[derive(Default)]
+pub struct ProjectWindows {
+	by_label: Mutex<HashMap<String, String>>,
+}
+
+impl ProjectWindows {
+	/// If the events of the project are for the window (i.e., its project window, or not a project window).
+	pub fn is_for(&self, label: &str, project_id: &str) -> bool {
+		match self.by_label.lock().get(label) {
+			Some(window_project_id) => window_project_id == project_id,
+			None => true,
+		}
+	}
+
+	fn insert(&self, label: &str, project_id: &str) {
+		self.by_label
+			.lock()
+			.insert(label.to_string(), project_id.to_string());
+	}
+
+	fn remove(&self, label: &str) {
+		self.by_label.lock().remove(label);
+	}
+}
+
+// endregion: --- ProjectWindows
+
+// region:    --- Project Window
+
+#[derive(TS, Serialize, Clone)]
+#[ts(export, export_to = "../src-ui/src/bindings/")]
+pub struct ProjectWindowData {
+	pub label: String,
+	pub project_id: ProjectId,
+}
+
+/// Open the window of the project, or focus it if already open.
+pub async fn open_project_window(
+	ctx: Arc<Ctx>,
+	app: AppHandle<Wry>,
+	project_id: ProjectId,
+) -> Result<ProjectWindowData> {
+	let label = project_window_label(&project_id);
+
+	match app.get_window(&label) {
+		Some(window) => focus(&window)?,
+		None => {
+			let project = ProjectBmc::get(ctx, &project_id).await?;
+			let url = WindowUrl::App(f!("index.html?project_id={project_id}").into());
+			let window = WindowBuilder::new(&app, &label, url)
+				.title(project.name)
+				.build()?;
+
+			let project_windows = (*app.state::<Arc<ProjectWindows>>()).clone();
+			project_windows.insert(&label, &project_id);
+			let window_label = label.clone();
+			window.on_window_event(move |evt| {
+				if let WindowEvent::Destroyed = evt {
+					project_windows.remove(&window_label);
+				}
+			});
+		}
+	}
+
+	Ok(ProjectWindowData { label, project_id })
+}
+
+/// Focus the window of the project, fails with `Error::WindowNotFound` if not open.
+pub fn focus_project_window(
+	app: AppHandle<Wry>,
+	project_id: ProjectId,
+) -> Result<ProjectWindowData> {
+	let label = project_window_label(&project_id);
+	let window = app
+		.get_window(&label)
+		.ok_or_else(|| Error::WindowNotFound(label.clone()))?;
+	focus(&window)?;
+
+	Ok(ProjectWindowData { label, project_id })
+}
+
+fn focus(window: &Window<Wry>) -> Result<()> {
+	window.unminimize()?;
+	window.set_focus()?;
+	Ok(())
+}
+
+/// Note: The window labels are alphanumeric with `-`, `/`, `:`, `_` (e.g., `project-window/project:abc`).
+fn project_window_label(project_id: &ProjectId) -> String {
+	f!("project-window/{project_id}")
+}
+
+// endregion: --- Project Window
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_project_windows_is_for() -> anyhow::Result<()> {
+		// --- FIXTURE
+		let project_windows = ProjectWindows::default();
+		project_windows.insert("project-window/project:a", "project:a");
+
+		// --- EXEC & CHECK
+		assert!(project_windows.is_for("project-window/project:a", "project:a"));
+		assert!(!project_windows.is_for("project-window/project:a", "project:b"));
+		assert!(
+			project_windows.is_for("main", "project:b"),
+			"not a project window"
+		);
+
+		project_windows.remove("project-window/project:a");
+		assert!(project_windows.is_for("project-window/project:a", "project:b"));
+
+		Ok(())
+	}
+}
+// endregion: --- Tests
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TaskId } from "./TaskId";
+
+export interface Attachment { id: string, ctime: string, task_id: TaskId, name: string, size: number, mime: string, hash: string, path: string, }
\ No newline at end of file
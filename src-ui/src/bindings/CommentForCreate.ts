@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TaskId } from "./TaskId";
+
+export interface CommentForCreate { task_id: TaskId, body: string, }
\ No newline at end of file
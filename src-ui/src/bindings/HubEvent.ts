@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export interface HubEvent<D> { hub: string, topic: string, label?: string, data?: D, }
\ No newline at end of file
+export interface HubEvent<D> { hub: string, topic: string, label?: string, seq?: number, request_id?: string, data?: D, }
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { IpcErrorCode } from "./IpcErrorCode";
+
+export interface IpcError { code: IpcErrorCode, message: string, field?: string, details?: any, }
\ No newline at end of file
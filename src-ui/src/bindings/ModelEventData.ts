@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface ModelEventData<E> { id: string, entity?: E, changed?: Array<string>, deleted?: E, }
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ModelEventData } from "./ModelEventData";
+
+export interface ModelManyEventData<E> { ids: Array<string>, items: Array<ModelEventData<E>>, }
\ No newline at end of file
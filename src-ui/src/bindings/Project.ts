@@ -1,3 +1,4 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProjectId } from "./ProjectId";
 
-export interface Project { id: string, name: string, ctime: string, }
\ No newline at end of file
+export interface Project { id: ProjectId, name: string, ctime: string, description?: string, color?: string, icon?: string, archived: boolean, template: boolean, }
\ No newline at end of file
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface ProjectForClone { name?: string, reset_done?: boolean, strip_desc?: boolean, template?: boolean, }
\ No newline at end of file
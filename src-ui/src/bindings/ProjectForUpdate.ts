@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export interface ProjectForUpdate { name?: string, }
\ No newline at end of file
+export interface ProjectForUpdate { name?: string, description?: string, color?: string, icon?: string, archived?: boolean, template?: boolean, }
\ No newline at end of file
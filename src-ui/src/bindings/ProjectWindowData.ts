@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProjectId } from "./ProjectId";
+
+export interface ProjectWindowData { label: string, project_id: ProjectId, }
\ No newline at end of file
@@ -1,3 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProjectId } from "./ProjectId";
+import type { TaskId } from "./TaskId";
 
-export interface Task { id: string, ctime: string, project_id: string, done: boolean, title: string, desc?: string, }
\ No newline at end of file
+export interface Task { id: TaskId, ctime: string, project_id: ProjectId, done: boolean, title: string, desc?: string, due_date?: string, recur_rule?: string, comment_count?: number, }
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TaskId } from "./TaskId";
+
+export interface TaskDepForCreate { task_id: TaskId, blocker_id: TaskId, }
\ No newline at end of file
@@ -1,3 +1,4 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProjectId } from "./ProjectId";
 
-export interface TaskForCreate { project_id: string, title: string, done?: boolean, desc?: string, }
\ No newline at end of file
+export interface TaskForCreate { project_id: ProjectId, title: string, done?: boolean, desc?: string, due_date?: string, recur_rule?: string, }
\ No newline at end of file
@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export interface TaskForUpdate { title?: string, done?: boolean, desc?: string, }
\ No newline at end of file
+export interface TaskForUpdate { title?: string, done?: boolean, desc?: string, due_date?: string, recur_rule?: string, }
\ No newline at end of file
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProjectId } from "./ProjectId";
+import type { TaskId } from "./TaskId";
+
+export interface TimeEntry { id: string, ctime: string, task_id: TaskId, project_id: ProjectId, start: string, stop?: string, duration?: number, note?: string, }
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TimeTotal } from "./TimeTotal";
+
+export interface TimeReport { from: string, to: string, tasks: Array<TimeTotal>, projects: Array<TimeTotal>, total: number, }
\ No newline at end of file
@@ -1,12 +1,43 @@
 //! Manualy maintained (for now)
 //! Re-export all bindings for import convenience
 
+export * from './Attachment.js';
+export * from './AttachmentForCreate.js';
+export * from './Comment.js';
+export * from './CommentForCreate.js';
+export * from './CommentForUpdate.js';
+export * from './EventsSinceData.js';
+export * from './FieldViolation.js';
 export * from './HubEvent.js';
+export * from './IpcError.js';
+export * from './IpcErrorCode.js';
+export * from './ListChangeData.js';
+export * from './ListSubscriptionData.js';
+export * from './ModelBatchEvent.js';
+export * from './ModelEventBatch.js';
+export * from './ModelEventData.js';
+export * from './ModelManyEventData.js';
+export * from './ModelMutateManyResultData.js';
 export * from './ModelMutateResultData.js';
 export * from './Project.js';
+export * from './ProjectForClone.js';
 export * from './ProjectForCreate.js';
 export * from './ProjectForUpdate.js';
+export * from './ProjectId.js';
+export * from './ProjectWindowData.js';
 export * from './Task.js';
 export * from './TaskForCreate.js';
 export * from './TaskForUpdate.js';
+export * from './TaskId.js';
+export * from './TaskDep.js';
+export * from './TaskDepForCreate.js';
+export * from './TimeEntry.js';
+export * from './TimeEntryForUpdate.js';
+export * from './TimeReport.js';
+export * from './TimeReportQuery.js';
+export * from './TimeTotal.js';
+export * from './TimerForStart.js';
+export * from './Workspace.js';
+export * from './WorkspaceForCreate.js';
+export * from './WorkspaceForUpdate.js';
 
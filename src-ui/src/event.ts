@@ -1,13 +1,46 @@
 import { Event as TauriEvent, listen } from '@tauri-apps/api/event';
 import { hub } from 'dom-native';
-import type { HubEvent } from './bindings/index.js';
+import type { EventsSinceData, HubEvent } from './bindings/index.js';
+import { ipc_invoke } from './ipc.js';
+
+// The seq of the last numbered (i.e., Model) event published
+let last_seq: number | undefined;
 
 // --- Bridge Tauri HubEvent events to dom-native hub/pub/sub event
 //     (optional, but allows to use hub("Data").sub(..) or
 //      @onHub("Data", topic, label) on BaseHTMLElement custom elements)
-listen("HubEvent", function (evt: TauriEvent<HubEvent<any>>) {
+listen("HubEvent", async function (evt: TauriEvent<HubEvent<any>>) {
 	const hubEvent = evt.payload;
 
+	if (hubEvent.seq != null) {
+		// Catch up on the missed events (if any) before this one
+		if (last_seq != null && hubEvent.seq > last_seq + 1) {
+			await catchUp(last_seq);
+		}
+		// Already published by the catch up
+		if (last_seq != null && hubEvent.seq <= last_seq) return;
+		last_seq = hubEvent.seq;
+	}
+
+	publish(hubEvent);
+})
+
+async function catchUp(seq: number) {
+	const since: EventsSinceData<HubEvent<any>> = await ipc_invoke("events_since", { seq }).then(res => res.data);
+
+	// The missed events are not in the backend log anymore, reload all of the data
+	if (since.resync) {
+		window.location.reload();
+		return;
+	}
+
+	for (const hubEvent of since.events) {
+		last_seq = hubEvent.seq;
+		publish(hubEvent);
+	}
+}
+
+function publish(hubEvent: HubEvent<any>) {
 	// Get or create the Hub by name (from dom-native)
 	//   (a Hub is a event bus namespace silo)
 	let _hub = hub(hubEvent.hub);
@@ -18,4 +51,4 @@ listen("HubEvent", function (evt: TauriEvent<HubEvent<any>>) {
 	} else {
 		_hub.pub(hubEvent.topic, hubEvent.data);
 	}
-})
\ No newline at end of file
+}
@@ -1,17 +1,45 @@
 import { invoke } from "@tauri-apps/api";
 import { deepFreeze } from 'utils-min';
+import { IpcError } from './bindings/index.js';
+
+/** Error thrown by `ipc_invoke`, with the structured backend error (branch on `ipcError.code`). */
+export class IpcFailure extends Error {
+	readonly ipcError: IpcError;
+	constructor(ipcError: IpcError) {
+		super(ipcError.message);
+		this.ipcError = ipcError;
+	}
+}
 
 /** 
  * Small wrapper on top of tauri api invoke
  * 
  * best-practice: Light and narrow external api abstraction. 
  */
-export async function ipc_invoke(method: string, params?: object): Promise<any> {
-	const response: any = await invoke(method, { params });
+export async function ipc_invoke(method: string, params?: object, request_id?: string): Promise<any> {
+	// Note: `request_id` is put on the Model events of the call (`HubEvent.request_id`), to recognize its echoes.
+	const response: any = await invoke(method, { params, requestId: request_id });
 	if (response.error != null) {
 		console.log('ERROR - ipc_invoke - ipc_invoke error', response);
-		throw new Error(response.error);
+		throw new IpcFailure(response.error);
 	} else {
 		return deepFreeze(response.result);
 	}
 }
+
+let request_count = 0;
+const request_prefix = Math.random().toString(36).slice(2, 8);
+
+/** New client request id (unique to this window), for the `request_id` of `ipc_invoke`. */
+export function new_request_id(): string {
+	request_count += 1;
+	return `${request_prefix}-${request_count}`;
+}
+
+/**
+ * JSON-RPC 2.0 call(s) over the single `rpc` command (a request object, or an array for a batch).
+ * Returns the raw JSON-RPC response(s) (null if only notifications).
+ */
+export async function ipc_rpc(requests: object | object[]): Promise<any> {
+	return deepFreeze(await invoke("rpc", { params: requests }));
+}
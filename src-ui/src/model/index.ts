@@ -1,5 +1,5 @@
 import { pruneEmpty } from 'utils-min';
-import { ModelMutateResultData, Project, ProjectForCreate, ProjectForUpdate, Task, TaskForCreate, TaskForUpdate } from '../bindings/index.js';
+import { ListSubscriptionData, ModelMutateResultData, Project, ProjectForCreate, ProjectForUpdate, ProjectId, ProjectWindowData, Task, TaskForCreate, TaskForUpdate, TaskId } from '../bindings/index.js';
 import { ensure_ModelMutateResultData } from '../bindings/type_asserts.js';
 import { ipc_invoke } from '../ipc.js';
 
@@ -9,8 +9,11 @@ import { ipc_invoke } from '../ipc.js';
  * - M - For the Enity model type (e.g., Project)
  * - C - For the Create data type (e.g., ProjectForCreate)
  * - U - For the update data type (e.g., ProjectForUpdate)
+ * - I - For the id type (e.g., ProjectId)
+ * 
+ * The mutations take an optional `request_id` (see `new_request_id`), put on their Model events.
  */
-class BaseFmc<M, C, U> {
+class BaseFmc<M, C, U, I extends string = string> {
   #cmd_suffix: string
   get cmd_suffix() { return this.#cmd_suffix; }
 
@@ -18,29 +21,29 @@ class BaseFmc<M, C, U> {
     this.#cmd_suffix = cmd_suffix;
   }
 
-  async get(id: string): Promise<M> {
+  async get(id: I): Promise<M> {
     return ipc_invoke(`get_${this.#cmd_suffix}`, { id }).then(res => res.data);
   }
 
-  async create(data: C): Promise<ModelMutateResultData> {
-    return ipc_invoke(`create_${this.#cmd_suffix}`, { data }).then(res => {
+  async create(data: C, request_id?: string): Promise<ModelMutateResultData> {
+    return ipc_invoke(`create_${this.#cmd_suffix}`, { data }, request_id).then(res => {
       return ensure_ModelMutateResultData(res.data);
     });
   }
 
-  async update(id: string, data: U): Promise<ModelMutateResultData> {
-    return ipc_invoke(`update_${this.#cmd_suffix}`, { id, data }).then(res => {
+  async update(id: I, data: U, request_id?: string): Promise<ModelMutateResultData> {
+    return ipc_invoke(`update_${this.#cmd_suffix}`, { id, data }, request_id).then(res => {
       return ensure_ModelMutateResultData(res.data);
     });
   }
 
-  async delete(id: string): Promise<ModelMutateResultData> {
-    return ipc_invoke(`delete_${this.#cmd_suffix}`, { id }).then(res => res.data);
+  async delete(id: I, request_id?: string): Promise<ModelMutateResultData> {
+    return ipc_invoke(`delete_${this.#cmd_suffix}`, { id }, request_id).then(res => res.data);
   }
 }
 
 // #region    --- ProjectFmc
-class ProjectFmc extends BaseFmc<Project, ProjectForCreate, ProjectForUpdate> {
+class ProjectFmc extends BaseFmc<Project, ProjectForCreate, ProjectForUpdate, ProjectId> {
   constructor() {
     super("project");
   }
@@ -49,12 +52,17 @@ class ProjectFmc extends BaseFmc<Project, ProjectForCreate, ProjectForUpdate> {
     // Note: for now, we just add a 's' for list, might might get rid of plurals
     return ipc_invoke(`list_${this.cmd_suffix}s`, {}).then(res => res.data);
   }
+
+  /** Open the window of the project (or focus it if already open) */
+  async openWindow(id: ProjectId): Promise<ProjectWindowData> {
+    return ipc_invoke("open_project_window", { id }).then(res => res.data);
+  }
 }
 export const projectFmc = new ProjectFmc();
 // #endregion --- ProjectFmc
 
 // #region    --- TaskBmc
-class TaskFmc extends BaseFmc<Task, TaskForCreate, TaskForUpdate> {
+class TaskFmc extends BaseFmc<Task, TaskForCreate, TaskForUpdate, TaskId> {
   constructor() {
     super("task");
   }
@@ -65,8 +73,24 @@ class TaskFmc extends BaseFmc<Task, TaskForCreate, TaskForUpdate> {
     // Note: for now, we just add a 's' for list, might might get rid of plurals
     return ipc_invoke(`list_${this.cmd_suffix}s`, { filter }).then(res => res.data);
   }
+
+  /** 
+   * Live list of the tasks matching the filter. The changes are published on the `Subscription` hub, 
+   * with the subscription id as topic (see `unsubscribe`).
+   */
+  async subscribeList(filter: any): Promise<ListSubscriptionData<Task>> {
+    filter = pruneEmpty(filter);
+    return ipc_invoke(`subscribe_list`, { entity: this.cmd_suffix, filter }).then(res => res.data);
+  }
 }
 export const taskFmc = new TaskFmc();
 
 // #endregion --- TaskBmc
 
+
+// #region    --- Subscription
+/** Stop a live list subscription (e.g., from `taskFmc.subscribeList`) */
+export async function unsubscribe(id: string): Promise<ModelMutateResultData> {
+  return ipc_invoke(`unsubscribe`, { id }).then(res => res.data);
+}
+// #endregion --- Subscription
@@ -1,4 +1,5 @@
 import { hub } from 'dom-native';
+import { ProjectId } from './bindings/index.js';
 
 const route_hub = hub("Route");
 
@@ -10,7 +11,7 @@ const route_hub = hub("Route");
  * 
  **/
 interface Route {
-	project_id?: string
+	project_id?: ProjectId
 }
 
 class Router {
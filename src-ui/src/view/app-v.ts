@@ -8,6 +8,7 @@
 //!
 
 import { BaseHTMLElement, customElement, elem, first, getFirst, html, onEvent, onHub } from 'dom-native';
+import { ProjectId } from '../bindings/index.js';
 import { projectFmc } from '../model';
 import { router } from '../router';
 
@@ -39,6 +40,12 @@ export class AppView extends BaseHTMLElement { // extends HTMLElement
 			this.#mainEl.textContent = "Welcome select project";
 		}
 	}
+
+	@onHub("Workspace", "workspace", "switch")
+	onWorkspaceSwitch() {
+		// all of the data changed, simplest is to reload the app
+		window.location.reload();
+	}
 	// #endregion --- App Events
 
 	// #region    --- UI Events
@@ -54,6 +61,14 @@ export class AppView extends BaseHTMLElement { // extends HTMLElement
 
 		this.#mainEl = getFirst(content, "main");
 
+		// project window (see `open_project_window`), only the project, without the nav
+		const project_id = new URLSearchParams(window.location.search).get("project_id");
+		if (project_id != null) {
+			getFirst(content, "nav-v").remove();
+			this.classList.add("project-window");
+			router.update_state({ project_id: project_id as ProjectId });
+		}
+
 		// beautify the header h1
 		const h1 = first(content, 'header > h1');
 		if (h1) {
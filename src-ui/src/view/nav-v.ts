@@ -1,6 +1,6 @@
 import { DInputElement } from '@dom-native/ui';
 import { all, BaseHTMLElement, customElement, elem, first, frag, html, on, OnEvent, onEvent, onHub, scanChild } from "dom-native";
-import { Project } from '../bindings/Project.js';
+import { ModelEventData, Project, ProjectId } from '../bindings/index.js';
 import { projectFmc } from '../model/index.js';
 import { router } from '../router.js';
 
@@ -22,13 +22,19 @@ export class NavView extends BaseHTMLElement { // extends HTMLElement
 
 	// #region    --- App Events
 	@onHub("Model", "project", "create")
-	async onProjectCreate(data: any) {
+	async onProjectCreate(data: ModelEventData<Project>) {
 		this.refreshContent();
 		router.update_state({
-			project_id: data.id
+			project_id: data.entity!.id
 		});
 	}
 
+	// Burst of project mutations (e.g., project clones), coalesced by the backend
+	@onHub("Model", "project", "batch")
+	onProjectBatch() {
+		this.refreshContent();
+	}
+
 	@onHub("Route", "change")
 	onRouteChange() {
 		this.updateContentSel();
@@ -64,10 +70,16 @@ export class NavView extends BaseHTMLElement { // extends HTMLElement
 	@onEvent("pointerdown", "section > a")
 	selNav(evt: Event & OnEvent) {
 
-		const project_id = evt.selectTarget.getAttribute("data-id")!;
+		const project_id = evt.selectTarget.getAttribute("data-id")! as ProjectId;
 
 		router.update_state({ project_id });
 	}
+
+	@onEvent("dblclick", "section > a")
+	openProjectWindow(evt: Event & OnEvent) {
+		const project_id = evt.selectTarget.getAttribute("data-id")! as ProjectId;
+		projectFmc.openWindow(project_id);
+	}
 	// #endregion --- UI Events
 
 	init() {
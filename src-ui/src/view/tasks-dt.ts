@@ -1,7 +1,7 @@
 import { DCheckElement } from '@dom-native/ui';
-import { all, BaseHTMLElement, customElement, elem, first, frag, html, on, OnEvent, onEvent, onHub, position, scanChild, trigger } from 'dom-native';
-import { ModelMutateResultData, Task } from '../bindings/index.js';
-import { taskFmc } from '../model/index.js';
+import { all, BaseHTMLElement, customElement, elem, first, frag, hub, html, on, OnEvent, onEvent, position, scanChild, trigger } from 'dom-native';
+import { ListChangeData, Task } from '../bindings/index.js';
+import { taskFmc, unsubscribe } from '../model/index.js';
 import { classable } from '../utils.js';
 
 const TASK_HEADER = html`
@@ -26,19 +26,32 @@ export class TasksDataTable extends BaseHTMLElement { // extends HTMLElement
 
 	#filter?: any
 	set filter(f: any) { this.#filter = f; this.update() }
+
+	/** The live list subscription of the displayed tasks (see `update`) */
+	#subscription_id?: string;
 	// #endregion --- Data
 
 	// #region    --- App Event
-	// Create will refresh the full datagrid, in case of sort by name and such
-	@onHub("Model", "task", "create")
-	onTaskCreate() {
-		this.update();
+	// The subscription only publishes the changes of the tasks matching the filter
+	// (rather than re-listing on any task Model event)
+	onListChange(data: ListChangeData<Task>) {
+		data.left.forEach(id => this.removeTaskRow(id));
+
+		data.changed.forEach(task => {
+			all(this, `task-row.${classable(task.id)}`).forEach((taskEl) => (<TaskRow>taskEl).task = task);
+		});
+
+		// Note: The list is sorted by newest first, so the new tasks go after the header
+		if (data.entered.length > 0) {
+			const content = frag(data.entered, task => elem('task-row', { $: { task } }));
+			const headerEls = all(this, '.th');
+			const lastHeaderEl = headerEls[headerEls.length - 1];
+			lastHeaderEl.after(content);
+		}
 	}
 
-	// Delete can be more selective in this case, will delete the row
-	@onHub("Model", "task", "delete")
-	onTaskDelete(data: ModelMutateResultData) {
-		all(this, `task-row.${classable(data.id)}`).forEach(taskRowEl => {
+	removeTaskRow(id: string) {
+		all(this, `task-row.${classable(id)}`).forEach(taskRowEl => {
 			// Note: This will add the class in the taskRow, but the animations are on the cells
 			//       as the task-row as the display: contents in the css 
 			//       (to be transparent to the grid layout, hence, can't style it)
@@ -60,12 +73,6 @@ export class TasksDataTable extends BaseHTMLElement { // extends HTMLElement
 			// });
 		});
 	}
-
-	@onHub("Model", "task", "update")
-	async onTaskUpdate(data: ModelMutateResultData) {
-		const newTask = await taskFmc.get(data.id);
-		all(this, `task-row.${classable(data.id)}`).forEach((taskEl) => (<TaskRow>taskEl).task = newTask);
-	}
 	// #endregion --- App Event
 
 	// #region    --- UI Events
@@ -116,13 +123,30 @@ export class TasksDataTable extends BaseHTMLElement { // extends HTMLElement
 		this.update();
 	}
 
+	disconnectedCallback() {
+		super.disconnectedCallback();
+		this.#unsubscribe();
+	}
+
+	#unsubscribe() {
+		const subscription_id = this.#subscription_id;
+		if (subscription_id != null) {
+			this.#subscription_id = undefined;
+			hub("Subscription").unsub(subscription_id);
+			unsubscribe(subscription_id);
+		}
+	}
+
 	async update() {
 		if (this.initialized) {
 			const filter = {
 				project_id: this.#project_id,
 				...this.#filter
 			}
-			const tasks = await taskFmc.list(filter);
+			this.#unsubscribe();
+			const { id, rows: tasks } = await taskFmc.subscribeList(filter);
+			this.#subscription_id = id;
+			hub("Subscription").sub(id, "change", (data: ListChangeData<Task>) => this.onListChange(data), { ns: id });
 
 			const content = frag(tasks, task => elem('task-row', { $: { task } }));
 